@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use crate::components::movement::Direction;
+use crate::resources::CharacterMetadata;
+
+/// 角色動畫種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationKind {
+    Idle,
+    Walk,
+    Attack,
+    Hurt,
+    Death,
+}
+
+impl AnimationKind {
+    /// 從描述檔的動畫名稱判斷種類，例如 "breathing-idle"、"walking-8-frames"、"taking-punch"
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+
+        // 受傷與死亡的名稱可能也含有攻擊的字眼（taking-punch），先判斷
+        if has(&["death", "dying", "die"]) {
+            Some(AnimationKind::Death)
+        } else if has(&["hurt", "hit", "taking", "damage"]) {
+            Some(AnimationKind::Hurt)
+        } else if has(&["attack", "slash", "swing", "punch", "kick", "strike"]) {
+            Some(AnimationKind::Attack)
+        } else if has(&["walk", "run"]) {
+            Some(AnimationKind::Walk)
+        } else if has(&["idle", "breathing", "stance"]) {
+            Some(AnimationKind::Idle)
+        } else {
+            None
+        }
+    }
+
+    /// 每秒影格數
+    pub fn fps(&self) -> f32 {
+        match self {
+            AnimationKind::Idle => 4.0,
+            AnimationKind::Walk => 10.0,
+            AnimationKind::Attack => 14.0,
+            AnimationKind::Hurt => 10.0,
+            AnimationKind::Death => 8.0,
+        }
+    }
+
+    /// 是否循環播放（不循環的動畫停在最後一格）
+    pub fn looping(&self) -> bool {
+        matches!(self, AnimationKind::Idle | AnimationKind::Walk)
+    }
+}
+
+/// 角色動畫狀態機 - 狀態系統依移動與戰鬥狀態設定種類與方向，動畫系統推進圖集上的影格
+#[derive(Component, Debug)]
+pub struct CharacterAnimation {
+    pub metadata: Handle<CharacterMetadata>,
+    pub kind: AnimationKind,
+    pub direction: Direction,
+    pub frame: usize,
+    pub timer: Timer,
+}
+
+impl CharacterAnimation {
+    pub fn new(metadata: Handle<CharacterMetadata>) -> Self {
+        Self {
+            metadata,
+            kind: AnimationKind::Idle,
+            direction: Direction::South,
+            frame: 0,
+            timer: Timer::from_seconds(1.0 / AnimationKind::Idle.fps(), TimerMode::Repeating),
+        }
+    }
+
+    /// 切換動畫；種類改變時從第一格重新播放，只改變方向時保留目前的影格
+    pub fn set(&mut self, kind: AnimationKind, direction: Direction) {
+        if self.kind != kind {
+            self.kind = kind;
+            self.frame = 0;
+            self.timer = Timer::from_seconds(1.0 / kind.fps(), TimerMode::Repeating);
+        }
+        self.direction = direction;
+    }
+}
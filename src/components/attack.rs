@@ -1,7 +1,14 @@
 use bevy::prelude::*;
-
-// 戰鬥狀態 (參考 GitHub 專案)
-#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+use std::collections::HashSet;
+use crate::components::status::StatusKind;
+use crate::states::InRun;
+use crate::constants::*;
+
+// 戰鬥狀態 (參考 GitHub 專案) - 只在一局遊戲中存在，每局從 Idle 開始
+//
+// Idle 是即時戰鬥；其他狀態是與一個敵人輪流出手的時機攻擊模式，即時的移動與敵人 AI 暫停
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(InRun = InRun)]
 pub enum CombatState {
     #[default]
     Idle,           // 非戰鬥狀態
@@ -11,13 +18,43 @@ pub enum CombatState {
 }
 
 // 攻擊時機 (參考 GitHub 專案)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AttackTiming {
     Early,
     Critical,  // 完美時機
     Late,
 }
 
+impl AttackTiming {
+    /// 依按下時在行動階段的進度（0 到 1）判定時機
+    pub fn from_ratio(ratio: f32) -> Self {
+        if ratio < TIMING_CRITICAL_WINDOW.0 {
+            AttackTiming::Early
+        } else if ratio > TIMING_CRITICAL_WINDOW.1 {
+            AttackTiming::Late
+        } else {
+            AttackTiming::Critical
+        }
+    }
+
+    /// 玩家攻擊的傷害倍數：完美時機雙倍傷害，差時機減半
+    pub fn attack_multiplier(timing: Option<AttackTiming>) -> f32 {
+        match timing {
+            Some(AttackTiming::Critical) => 2.0,
+            Some(AttackTiming::Early | AttackTiming::Late) | None => 0.5,
+        }
+    }
+
+    /// 玩家格擋後受到的傷害倍數：完美時機完全擋下，差時機擋下一半，沒按就全部吃下
+    pub fn parry_multiplier(timing: Option<AttackTiming>) -> f32 {
+        match timing {
+            Some(AttackTiming::Critical) => 0.0,
+            Some(AttackTiming::Early | AttackTiming::Late) => 0.5,
+            None => 1.0,
+        }
+    }
+}
+
 // 攻擊階段 (參考 GitHub 專案)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AttackStage {
@@ -26,61 +63,37 @@ pub enum AttackStage {
     CoolDown, // 冷卻階段
 }
 
-// 攻擊事件
-#[derive(Event)]
-pub struct AttackEvent {
-    pub position: Vec3,
-    pub direction: Vec2,
-    pub damage: i32,
-    pub weapon_type: WeaponType,
-    pub timing: Option<AttackTiming>, // 新增時機判定
-}
-
 // 武器類型
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum WeaponType {
     Sword,
     Magic,
     Arrow,
 }
 
-// 子彈/投射物相關 Components
+impl WeaponType {
+    /// 切換武器時的順序
+    pub const ALL: [WeaponType; 3] = [WeaponType::Sword, WeaponType::Magic, WeaponType::Arrow];
+}
+
+// 子彈/投射物相關 Components（命中判定使用同一個實體上的 Hitbox）
 #[derive(Component)]
 pub struct Projectile {
     pub velocity: Vec2,
     pub lifetime: Timer,
     pub damage: i32,
+    pub weapon_type: WeaponType,  // 追蹤與命中效果依武器設定
+    pub pierce: u32,              // 剩下可以穿過的目標數
+    pub bounces: u32,             // 剩下可以反彈的次數
 }
 
+// 投射物命中效果 - 放大並淡出後消失
 #[derive(Component)]
-pub struct AttackAnimation {
+pub struct ImpactEffect {
     pub timer: Timer,
-    pub is_attacking: bool,
 }
 
-// 攻擊類型
-#[derive(Component)]
-pub enum AttackType {
-    Melee,   // 近戰攻擊
-    Ranged,  // 遠程攻擊（子彈）
-}
-
-// 近戰攻擊組件
-#[derive(Component)]
-pub struct MeleeAttack {
-    pub lifetime: Timer,
-    pub damage: i32,
-}
-
-// 武器效果組件
-#[derive(Component)]
-pub struct WeaponEffect {
-    pub lifetime: Timer,
-    pub weapon_type: WeaponType,
-    pub scale_animation: bool,
-}
-
-// 攻擊時機系統 (參考 GitHub 專案)
+// 攻擊時機系統 (參考 GitHub 專案) - 掛在目前出手的一方（玩家或敵人）
 #[derive(Component)]
 pub struct TimingAttack {
     pub stage: AttackStage,
@@ -88,8 +101,33 @@ pub struct TimingAttack {
     pub action_timer: Timer,
     pub cooldown_timer: Timer,
     pub damage_multiplier: f32, // 基於時機的傷害倍數
+    pub timing: Option<AttackTiming>, // 玩家按下的時機（沒按時為 None）
+}
+
+impl Default for TimingAttack {
+    fn default() -> Self {
+        Self {
+            stage: AttackStage::Warmup,
+            warmup_timer: Timer::from_seconds(TIMING_WARMUP_DURATION, TimerMode::Once),
+            action_timer: Timer::from_seconds(TIMING_ACTION_DURATION, TimerMode::Once),
+            cooldown_timer: Timer::from_seconds(TIMING_COOLDOWN_DURATION, TimerMode::Once),
+            damage_multiplier: 1.0,
+            timing: None,
+        }
+    }
 }
 
+impl TimingAttack {
+    /// 行動階段的進度（0 到 1），時機條的游標位置
+    pub fn action_progress(&self) -> f32 {
+        self.action_timer.fraction()
+    }
+}
+
+// 時機攻擊的對手 - 掛在玩家身上，回到即時戰鬥時移除
+#[derive(Component)]
+pub struct TimingOpponent(pub Entity);
+
 // 武器選擇 UI (參考 GitHub 專案)
 #[derive(Component)]
 pub struct WeaponSelector {
@@ -108,8 +146,8 @@ pub struct Weapon {
 // 武器圖片資源組件 - 儲存左右兩側的圖片Handle
 #[derive(Component)]
 pub struct WeaponSprites {
-    pub right_sprite: Handle<Image>,  // 右側/默認圖片
-    pub left_sprite: Handle<Image>,   // 左側圖片
+    pub right_sprite: Handle<Image>,          // 右側/默認圖片
+    pub left_sprite: Option<Handle<Image>>,   // 左側圖片；沒有時翻轉右側圖片
 }
 
 // 攻擊動畫組件 - 處理武器揮擊
@@ -133,4 +171,69 @@ pub struct WeaponOffset {
     pub position: Vec2,     // 相對於玩家的位置偏移
     pub base_angle: f32,    // 基礎角度（弧度）
     pub z_layer: f32,       // Z 層級（前景/背景）
-}
\ No newline at end of file
+    pub hand: String,       // 對齊的手部關鍵點名稱
+    pub grip: f32,          // 圖片中心沿面向離手的距離
+    pub aim: bool,          // 是否朝面向旋轉（否則直立拿著）
+}
+
+// === 命中判定系統 ===
+
+// 攻擊判定框 - 掛在武器上，跟著揮擊弧線移動
+#[derive(Component)]
+pub struct Hitbox {
+    pub owner: Entity,                  // 攻擊者（傷害取自其 AttackDamage）
+    pub reach: f32,                     // 判定中心沿武器方向的距離（武器本地座標）
+    pub radius: f32,                    // 判定半徑（世界座標）
+    pub knockback: f32,                 // 擊退力道
+    pub damage_multiplier: f32,         // 乘上攻擊者的攻擊力
+    pub status: Option<StatusKind>,     // 命中時附加的狀態效果
+    pub hit_entities: HashSet<Entity>,  // 本次揮擊已命中的目標，確保每次揮擊只命中一次
+}
+
+// 受擊判定框
+#[derive(Component)]
+pub struct Hurtbox {
+    pub radius: f32,
+}
+
+// 傷害事件
+#[derive(Event)]
+pub struct DamageEvent {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub amount: i32,
+    pub knockback: Vec2,  // 擊退速度向量
+    pub source: DamageSource,
+}
+
+// 傷害來源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    Hit,     // 攻擊命中
+    Status,  // 狀態效果的持續傷害，沒有打擊感
+}
+
+// 擊退組件 - 受擊後短暫被推開
+#[derive(Component)]
+pub struct Knockback {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing_windows_and_multipliers() {
+        assert_eq!(AttackTiming::from_ratio(0.0), AttackTiming::Early);
+        assert_eq!(AttackTiming::from_ratio(0.5), AttackTiming::Critical);
+        assert_eq!(AttackTiming::from_ratio(1.0), AttackTiming::Late);
+
+        assert_eq!(AttackTiming::attack_multiplier(Some(AttackTiming::Critical)), 2.0);
+        assert_eq!(AttackTiming::attack_multiplier(None), 0.5);
+        assert_eq!(AttackTiming::parry_multiplier(Some(AttackTiming::Critical)), 0.0);
+        assert_eq!(AttackTiming::parry_multiplier(Some(AttackTiming::Late)), 0.5);
+        assert_eq!(AttackTiming::parry_multiplier(None), 1.0);
+    }
+}
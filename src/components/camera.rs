@@ -3,4 +3,37 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct CameraFollow {
     pub speed: f32,
-}
\ No newline at end of file
+    pub look_ahead: f32,  // 往玩家面向的方向多看的距離
+    pub dead_zone: Vec2,  // 目標在此半寬高範圍內移動時相機不動
+}
+
+/// 換房間時的鏡頭平移 - 從起點平滑移到新房間的目標位置，結束後移除
+#[derive(Component)]
+pub struct CameraPan {
+    pub from: Vec2,
+    pub timer: Timer,
+}
+/// 房間切換時覆蓋整個畫面的淡出淡入遮罩
+#[derive(Component)]
+pub struct ScreenFade;
+
+/// 相機效果事件 - 戰鬥、爆炸、關門等系統送出，效果疊加在跟隨位置上，不影響 CameraFollow
+#[derive(Event, Debug, Clone, Copy)]
+pub enum CameraImpulseEvent {
+    /// 增加震動強度（0–1，會累加並逐漸衰減，實際震幅是強度的平方）
+    Shake { trauma: f32 },
+    /// 短暫凍結遊戲時間（真實秒數）
+    HitStop { duration: f32 },
+    /// 往指定方向推一下相機後彈回
+    Kick { direction: Vec2, strength: f32 },
+}
+
+/// 相機效果狀態
+#[derive(Component, Default, Debug)]
+pub struct CameraEffects {
+    pub trauma: f32,
+    pub kick: Vec2,
+    pub hit_stop: f32,  // 剩餘的凍結時間
+    pub elapsed: f32,   // 震動雜訊的時間軸
+    pub offset: Vec2,   // 目前疊加在相機位置上的偏移
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// 碰撞箱組件 - 與牆壁格子做 AABB 碰撞（玩家、敵人、投射物共用）
+#[derive(Component)]
+pub struct Collider {
+    pub half_size: Vec2,  // 碰撞箱半寬高（世界座標）
+}
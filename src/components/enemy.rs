@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use crate::components::inventory::ItemKind;
+use crate::components::status::StatusKind;
+
+// 敵人標記組件
+#[derive(Component)]
+pub struct Enemy {
+    pub kind: EnemyKind,
+    pub room: usize,  // 所屬房間在 RoomGraph 中的索引
+}
+
+// 敵人種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyKind {
+    Slime,     // 史萊姆：慢、血少、近身撞擊
+    Skeleton,  // 骷髏戰士：較快、血多、攻擊較痛
+}
+
+impl EnemyKind {
+    /// assets/characters 底下的角色資料夾名稱
+    pub fn character_name(&self) -> &'static str {
+        match self {
+            EnemyKind::Slime => "green_slime",
+            EnemyKind::Skeleton => "skeleton_warrior",
+        }
+    }
+
+    pub fn max_health(&self) -> i32 {
+        match self {
+            EnemyKind::Slime => 40,
+            EnemyKind::Skeleton => 75,
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 80.0,
+            EnemyKind::Skeleton => 130.0,
+        }
+    }
+
+    pub fn damage(&self) -> i32 {
+        match self {
+            EnemyKind::Slime => 5,
+            EnemyKind::Skeleton => 12,
+        }
+    }
+
+    pub fn sight_range(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 200.0,
+            EnemyKind::Skeleton => 320.0,
+        }
+    }
+
+    pub fn attack_range(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 40.0,
+            EnemyKind::Skeleton => 56.0,
+        }
+    }
+
+    pub fn attack_cooldown(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 1.2,
+            EnemyKind::Skeleton => 0.9,
+        }
+    }
+
+    pub fn hurtbox_radius(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 20.0,
+            EnemyKind::Skeleton => 24.0,
+        }
+    }
+
+    pub fn collider_half_size(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 12.0,
+            EnemyKind::Skeleton => 14.0,
+        }
+    }
+
+    /// 擊倒時玩家獲得的經驗值
+    pub fn experience(&self) -> u32 {
+        match self {
+            EnemyKind::Slime => 10,
+            EnemyKind::Skeleton => 25,
+        }
+    }
+
+    /// 掉落表：(物品, 數量, 機率)，每一項各自判定
+    pub fn loot(&self) -> &'static [(ItemKind, u32, f64)] {
+        match self {
+            EnemyKind::Slime => &[(ItemKind::Gold, 3, 0.6), (ItemKind::Potion, 1, 0.1)],
+            EnemyKind::Skeleton => &[(ItemKind::Gold, 8, 0.8), (ItemKind::Potion, 1, 0.25), (ItemKind::Key, 1, 0.1)],
+        }
+    }
+
+    /// 不會受到的狀態效果
+    pub fn status_immunities(&self) -> &'static [StatusKind] {
+        match self {
+            EnemyKind::Slime => &[],
+            EnemyKind::Skeleton => &[StatusKind::Poison],  // 不死生物不會中毒
+        }
+    }
+
+    /// 血量比例低於此值時逃跑（0.0 代表永不逃跑）
+    pub fn flee_threshold(&self) -> f32 {
+        match self {
+            EnemyKind::Slime => 0.0,
+            EnemyKind::Skeleton => 0.25,
+        }
+    }
+}
+
+// 敵人 AI 狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyState {
+    Patrol,  // 在房間內巡邏
+    Chase,   // 追擊玩家
+    Attack,  // 攻擊範圍內，攻擊玩家
+    Flee,    // 血量過低，遠離玩家
+}
+
+// 敵人 AI 組件
+#[derive(Component)]
+pub struct EnemyAi {
+    pub state: EnemyState,
+    pub home: Rect,            // 所屬房間的可行走範圍（世界座標）
+    pub patrol_target: Vec2,   // 當前巡邏目標點
+    pub patrol_timer: Timer,   // 到時間就換下一個巡邏點
+}
+
+// 敵人攻擊冷卻
+#[derive(Component)]
+pub struct EnemyAttackCooldown {
+    pub timer: Timer,
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// 可以互動的物件（門、寶箱、拉桿、NPC…）
+///
+/// 玩家在 `radius` 內時，最近的一個會顯示提示，按下互動鍵就對它送出 `InteractEvent`
+#[derive(Component, Debug, Clone)]
+pub struct Interactable {
+    pub radius: f32,            // 互動距離（世界座標）
+    pub prompt: String,         // 顯示在物件上方的動作名稱
+    pub requires_facing: bool,  // 玩家必須面向物件才能互動（例如門）
+}
+
+/// 玩家對某個物件按下互動鍵；各種物件的系統只處理自己的實體
+#[derive(Event, Debug)]
+pub struct InteractEvent {
+    pub target: Entity,
+}
+
+/// 物件上方的互動提示文字
+#[derive(Component)]
+pub struct InteractionPrompt;
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::attack::WeaponType;
+
+/// 物品種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemKind {
+    Potion,              // 回復生命
+    Key,                 // 打開上鎖的門
+    Gold,
+    Weapon(WeaponType),
+}
+
+impl ItemKind {
+    /// 物品欄中同一種物品最多能疊幾個
+    pub fn max_stack(&self) -> u32 {
+        match self {
+            ItemKind::Potion => 5,
+            ItemKind::Key => 9,
+            ItemKind::Gold => 999,
+            ItemKind::Weapon(_) => 1,
+        }
+    }
+
+    /// 走到旁邊就自動撿起；否則要按互動鍵
+    pub fn auto_collect(&self) -> bool {
+        !matches!(self, ItemKind::Weapon(_))
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemKind::Potion => "Potion",
+            ItemKind::Key => "Key",
+            ItemKind::Gold => "Gold",
+            ItemKind::Weapon(WeaponType::Sword) => "Sword",
+            ItemKind::Weapon(WeaponType::Magic) => "Magic Staff",
+            ItemKind::Weapon(WeaponType::Arrow) => "Bow",
+        }
+    }
+
+    /// 地上物品的顏色（還沒有物品圖片）
+    pub fn color(&self) -> Color {
+        match self {
+            ItemKind::Potion => Color::srgb(0.9, 0.2, 0.3),
+            ItemKind::Key => Color::srgb(0.95, 0.85, 0.3),
+            ItemKind::Gold => Color::srgb(1.0, 0.7, 0.1),
+            ItemKind::Weapon(_) => Color::srgb(0.6, 0.8, 1.0),
+        }
+    }
+}
+
+/// 物品欄中的一格
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ItemStack {
+    pub item: ItemKind,
+    pub count: u32,
+}
+
+/// 玩家的物品欄 - 每種物品一格，數量不超過堆疊上限
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+}
+
+impl Inventory {
+    pub fn count(&self, item: ItemKind) -> u32 {
+        self.stacks.iter().find(|stack| stack.item == item).map_or(0, |stack| stack.count)
+    }
+
+    /// 加入物品，回傳實際加入的數量（超過堆疊上限的部分不加入）
+    pub fn add(&mut self, item: ItemKind, count: u32) -> u32 {
+        let index = match self.stacks.iter().position(|stack| stack.item == item) {
+            Some(index) => index,
+            None => {
+                self.stacks.push(ItemStack { item, count: 0 });
+                self.stacks.len() - 1
+            }
+        };
+        let stack = &mut self.stacks[index];
+        let added = count.min(item.max_stack().saturating_sub(stack.count));
+        stack.count += added;
+        if stack.count == 0 {
+            self.stacks.remove(index);
+        }
+        added
+    }
+
+    /// 移除物品；數量不夠時不移除並回傳 false
+    pub fn remove(&mut self, item: ItemKind, count: u32) -> bool {
+        let Some(index) = self.stacks.iter().position(|stack| stack.item == item && stack.count >= count) else {
+            return false;
+        };
+        self.stacks[index].count -= count;
+        if self.stacks[index].count == 0 {
+            self.stacks.remove(index);
+        }
+        true
+    }
+}
+
+/// 地上可以撿起的物品
+#[derive(Component, Debug)]
+pub struct ItemPickup {
+    pub item: ItemKind,
+    pub count: u32,
+    pub room: usize,            // 所屬房間（只在目前房間顯示）
+    pub origin: Option<IVec2>,  // 樓層生成時放置的格子；敵人掉落的物品沒有
+}
+
+/// 物品欄的變化（UI 與存檔依此更新）
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub enum InventoryEvent {
+    Added { item: ItemKind, count: u32 },
+    Removed { item: ItemKind, count: u32 },
+    /// 使用物品（喝藥水、用鑰匙開門），同時從物品欄移除一個
+    Used { item: ItemKind },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_are_capped_and_removed_when_empty() {
+        let mut inventory = Inventory::default();
+        assert_eq!(inventory.add(ItemKind::Potion, 3), 3);
+        assert_eq!(inventory.add(ItemKind::Potion, 4), 2);
+        assert_eq!(inventory.count(ItemKind::Potion), 5);
+        assert_eq!(inventory.add(ItemKind::Weapon(WeaponType::Magic), 1), 1);
+        assert_eq!(inventory.add(ItemKind::Weapon(WeaponType::Magic), 1), 0);
+
+        assert!(!inventory.remove(ItemKind::Key, 1));
+        assert!(!inventory.remove(ItemKind::Potion, 6));
+        assert!(inventory.remove(ItemKind::Potion, 5));
+        assert_eq!(inventory.count(ItemKind::Potion), 0);
+        assert_eq!(inventory.stacks.len(), 1);
+
+        // 加入 0 個不會留下空的格子
+        assert_eq!(inventory.add(ItemKind::Gold, 0), 0);
+        assert_eq!(inventory.stacks.len(), 1);
+    }
+}
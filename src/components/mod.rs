@@ -4,10 +4,22 @@ pub mod player;
 pub mod movement;
 pub mod camera;
 pub mod world;
+pub mod enemy;
+pub mod collision;
+pub mod interaction;
+pub mod animation;
+pub mod inventory;
+pub mod status;
 
 // Re-export all components for easy importing
 pub use attack::*;
 pub use player::*;
 pub use movement::*;
 pub use camera::*;
-pub use world::*;
\ No newline at end of file
+pub use world::*;
+pub use enemy::*;
+pub use collision::*;
+pub use interaction::*;
+pub use animation::*;
+pub use inventory::*;
+pub use status::*;
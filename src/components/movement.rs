@@ -14,19 +14,21 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn get_sprite_path(&self) -> &str {
-        match self {
-            Direction::North => "characters/knight_hero/rotations/north.png",
-            Direction::NorthEast => "characters/knight_hero/rotations/north-east.png",
-            Direction::East => "characters/knight_hero/rotations/east.png",
-            Direction::SouthEast => "characters/knight_hero/rotations/south-east.png",
-            Direction::South => "characters/knight_hero/rotations/south.png",
-            Direction::SouthWest => "characters/knight_hero/rotations/south-west.png",
-            Direction::West => "characters/knight_hero/rotations/west.png",
-            Direction::NorthWest => "characters/knight_hero/rotations/north-west.png",
+    /// 描述檔使用的方向名稱（south、north-east…）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "north" => Some(Direction::North),
+            "north-east" => Some(Direction::NorthEast),
+            "east" => Some(Direction::East),
+            "south-east" => Some(Direction::SouthEast),
+            "south" => Some(Direction::South),
+            "south-west" => Some(Direction::SouthWest),
+            "west" => Some(Direction::West),
+            "north-west" => Some(Direction::NorthWest),
+            _ => None,
         }
     }
-    
+
     pub fn from_input_vector(input: Vec2) -> Option<Self> {
         // deadzone 檢查在正規化之前，避免微小輸入被放大
         if input.length() < INPUT_DEADZONE {
@@ -19,6 +19,18 @@ pub struct Speed(pub f32);
 #[derive(Component)]
 pub struct InputVector(pub Vec2);
 
+#[derive(Component)]
+pub struct DashCooldown {
+    pub timer: Timer,
+}
+
+/// 衝刺中 - 朝固定方向高速移動，時間到就移除
+#[derive(Component)]
+pub struct Dash {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
 // 攻擊相關 Components
 #[derive(Component)]
 pub struct AttackCooldown {
@@ -31,6 +43,19 @@ pub struct AttackDamage(pub i32);
 #[derive(Component)]
 pub struct AttackRange(pub f32);
 
+/// 等級與累積經驗值（能力值依 PlayerArchetype 的等級表）
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Experience {
+    pub level: u32,
+    pub xp: u32,
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self { level: 1, xp: 0 }
+    }
+}
+
 #[derive(Component)]
 pub struct CurrentWeapon {
     pub weapon_type: WeaponType,
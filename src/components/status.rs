@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// 狀態效果種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusKind {
+    Burn,    // 燃燒：快速的持續傷害
+    Poison,  // 中毒：緩慢的持續傷害，攻擊力下降
+    Slow,    // 緩速：移動速度下降
+    Stun,    // 暈眩：不能移動與攻擊，結束後短暫免疫
+    Regen,   // 再生：持續回復生命
+}
+
+/// 一種狀態效果的數值
+#[derive(Debug, Clone, Copy)]
+pub struct StatusDefinition {
+    pub duration: f32,           // 持續時間；重複附加時刷新
+    pub max_stacks: u32,         // 重複附加時最多疊幾層
+    pub tick_interval: f32,      // 生命變化的間隔
+    pub tick_health: i32,        // 每層每次的生命變化（負數為傷害）
+    pub speed_multiplier: f32,   // 每層乘上的移動速度倍數
+    pub damage_multiplier: f32,  // 每層乘上的攻擊力倍數
+    pub stun: bool,
+    pub immunity_after: f32,     // 結束後免疫同一效果的秒數
+    pub tint: Color,             // 效果期間角色圖片的顏色
+}
+
+impl StatusKind {
+    pub fn definition(&self) -> StatusDefinition {
+        let base = StatusDefinition {
+            duration: 1.0,
+            max_stacks: 1,
+            tick_interval: 1.0,
+            tick_health: 0,
+            speed_multiplier: 1.0,
+            damage_multiplier: 1.0,
+            stun: false,
+            immunity_after: 0.0,
+            tint: Color::WHITE,
+        };
+        match self {
+            StatusKind::Burn => StatusDefinition {
+                duration: 3.0,
+                max_stacks: 3,
+                tick_interval: 0.5,
+                tick_health: -2,
+                tint: Color::srgb(1.0, 0.55, 0.3),
+                ..base
+            },
+            StatusKind::Poison => StatusDefinition {
+                duration: 6.0,
+                max_stacks: 5,
+                tick_health: -1,
+                damage_multiplier: 0.9,
+                tint: Color::srgb(0.55, 1.0, 0.45),
+                ..base
+            },
+            StatusKind::Slow => StatusDefinition {
+                duration: 2.0,
+                max_stacks: 3,
+                speed_multiplier: 0.7,
+                tint: Color::srgb(0.6, 0.75, 1.0),
+                ..base
+            },
+            StatusKind::Stun => StatusDefinition {
+                duration: 1.0,
+                speed_multiplier: 0.0,
+                stun: true,
+                immunity_after: 3.0,
+                tint: Color::srgb(1.0, 1.0, 0.45),
+                ..base
+            },
+            StatusKind::Regen => StatusDefinition {
+                duration: 5.0,
+                tick_health: 3,
+                tint: Color::srgb(1.0, 0.8, 0.9),
+                ..base
+            },
+        }
+    }
+}
+
+/// 身上的一個狀態效果
+#[derive(Debug, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub stacks: u32,
+    pub duration: Timer,
+    pub tick: Timer,
+}
+
+/// 角色身上的狀態效果與免疫
+///
+/// Speed 與 AttackDamage 保持基礎數值（由等級或敵人種類決定），
+/// 使用的系統再乘上這裡的倍數，效果結束時直接移除，不需要還原能力值
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    pub immunities: HashSet<StatusKind>,         // 永久免疫
+    pub cooldowns: Vec<(StatusKind, Timer)>,     // 效果結束後的暫時免疫
+}
+
+/// 附加狀態效果 - 命中時由判定框的 status 送出
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ApplyStatusEvent {
+    pub target: Entity,
+    pub kind: StatusKind,
+}
+
+impl StatusEffects {
+    pub fn with_immunities(kinds: &[StatusKind]) -> Self {
+        Self {
+            immunities: kinds.iter().copied().collect(),
+            ..default()
+        }
+    }
+
+    pub fn is_immune(&self, kind: StatusKind) -> bool {
+        self.immunities.contains(&kind) || self.cooldowns.iter().any(|(cooldown, _)| *cooldown == kind)
+    }
+
+    /// 附加效果：已經有同一效果時加一層（不超過上限）並刷新時間；免疫時回傳 false
+    pub fn apply(&mut self, kind: StatusKind) -> bool {
+        if self.is_immune(kind) {
+            return false;
+        }
+        let definition = kind.definition();
+        match self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            Some(effect) => {
+                effect.stacks = (effect.stacks + 1).min(definition.max_stacks);
+                effect.duration.reset();
+            }
+            None => self.effects.push(StatusEffect {
+                kind,
+                stacks: 1,
+                duration: Timer::from_seconds(definition.duration, TimerMode::Once),
+                tick: Timer::from_seconds(definition.tick_interval, TimerMode::Repeating),
+            }),
+        }
+        true
+    }
+
+    /// 經過 delta 時間，回傳這段時間的生命變化；結束的效果移除並開始暫時免疫
+    pub fn tick(&mut self, delta: Duration) -> i32 {
+        self.cooldowns.retain_mut(|(_, timer)| !timer.tick(delta).finished());
+
+        let mut health_change = 0;
+        let mut expired = Vec::new();
+        for effect in &mut self.effects {
+            let definition = effect.kind.definition();
+            let ticks = effect.tick.tick(delta).times_finished_this_tick() as i32;
+            health_change += definition.tick_health * effect.stacks as i32 * ticks;
+
+            if effect.duration.tick(delta).finished() {
+                expired.push(effect.kind);
+                if definition.immunity_after > 0.0 {
+                    self.cooldowns.push((effect.kind, Timer::from_seconds(definition.immunity_after, TimerMode::Once)));
+                }
+            }
+        }
+        self.effects.retain(|effect| !expired.contains(&effect.kind));
+        health_change
+    }
+
+    /// 移動速度倍數；暈眩時為 0
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects.iter()
+            .map(|effect| effect.kind.definition().speed_multiplier.powi(effect.stacks as i32))
+            .product()
+    }
+
+    /// 攻擊力倍數
+    pub fn damage_multiplier(&self) -> f32 {
+        self.effects.iter()
+            .map(|effect| effect.kind.definition().damage_multiplier.powi(effect.stacks as i32))
+            .product()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.effects.iter().any(|effect| effect.kind.definition().stun)
+    }
+
+    /// 角色圖片的顏色：最後附加的效果優先，沒有效果時為白色
+    pub fn tint(&self) -> Color {
+        self.effects.last().map_or(Color::WHITE, |effect| effect.kind.definition().tint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effects_stack_tick_expire_and_respect_immunities() {
+        let mut effects = StatusEffects::with_immunities(&[StatusKind::Poison]);
+        assert!(!effects.apply(StatusKind::Poison));
+
+        // 燃燒疊到上限三層，每 0.5 秒每層 2 點傷害
+        for _ in 0..4 {
+            assert!(effects.apply(StatusKind::Burn));
+        }
+        assert_eq!(effects.effects[0].stacks, 3);
+        assert_eq!(effects.tick(Duration::from_secs_f32(1.0)), -12);
+        assert_eq!(effects.tint(), StatusKind::Burn.definition().tint);
+
+        effects.apply(StatusKind::Slow);
+        effects.apply(StatusKind::Slow);
+        assert!((effects.speed_multiplier() - 0.49).abs() < 1e-5);
+
+        // 時間到就移除，能力值倍數回到 1
+        effects.tick(Duration::from_secs_f32(2.5));
+        assert!(effects.effects.is_empty());
+        assert_eq!(effects.speed_multiplier(), 1.0);
+        assert_eq!(effects.tint(), Color::WHITE);
+
+        // 暈眩結束後短暫免疫
+        effects.apply(StatusKind::Stun);
+        assert!(effects.is_stunned() && effects.speed_multiplier() == 0.0);
+        effects.tick(Duration::from_secs_f32(1.0));
+        assert!(!effects.is_stunned());
+        assert!(!effects.apply(StatusKind::Stun));
+        effects.tick(Duration::from_secs_f32(3.0));
+        assert!(effects.apply(StatusKind::Stun));
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 #[derive(Component)]
 pub struct GridTile;
@@ -30,9 +31,79 @@ pub enum RoomTileType {
     DoorOpen,           // 開啟的門
 }
 
+impl RoomTileType {
+    pub fn is_door(&self) -> bool {
+        matches!(self, RoomTileType::DoorClosed | RoomTileType::DoorOpen)
+    }
+
+    pub fn is_wall(&self) -> bool {
+        !self.is_door() && *self != RoomTileType::Floor
+    }
+
+    /// 同一格重疊時的優先度：門 > 地板 > 牆壁
+    fn priority(&self) -> u8 {
+        if self.is_door() {
+            2
+        } else if *self == RoomTileType::Floor {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// 瓷磚格 - 純資料的佈局，每個格子只會有一種瓷磚，生成實體前先在這裡合併
+#[derive(Debug, Clone, Default)]
+pub struct TileGrid {
+    pub tiles: HashMap<IVec2, RoomTileType>,
+}
+
+impl TileGrid {
+    /// 放置瓷磚；格子已有瓷磚時保留優先度較高的（同優先度保留先放的）
+    pub fn place(&mut self, cell: IVec2, tile_type: RoomTileType) {
+        match self.tiles.get(&cell) {
+            Some(existing) if existing.priority() >= tile_type.priority() => {}
+            _ => {
+                self.tiles.insert(cell, tile_type);
+            }
+        }
+    }
+
+    pub fn get(&self, cell: IVec2) -> Option<RoomTileType> {
+        self.tiles.get(&cell).copied()
+    }
+
+    /// 可以走進去的格子（地板與門）；只有檢查連通性的測試用到
+    #[cfg(test)]
+    pub fn is_walkable(&self, cell: IVec2) -> bool {
+        self.get(cell).is_some_and(|tile_type| !tile_type.is_wall())
+    }
+}
+
 #[derive(Component, Debug)]
 pub struct Door {
     pub is_open: bool,
+    pub cell: IVec2,          // 門所在的格子
+    pub room: Option<usize>,  // 門所屬的房間（走廊另一端的門屬於另一個房間）
+    pub key_locked: bool,     // 需要鑰匙才能打開
+    pub sealed: bool,         // 房間內還有敵人，暫時封鎖
+    pub link: Option<IVec2>,  // 走廊另一端相連的門
+    pub outward: IVec2,       // 從房間穿過門走向走廊的方向
+}
+
+impl Door {
+    pub fn new(cell: IVec2, is_open: bool) -> Self {
+        Self { is_open, cell, room: None, key_locked: false, sealed: false, link: None, outward: IVec2::ZERO }
+    }
+}
+
+/// 瓷磚屬於樓層的哪個區域，用來只顯示玩家所在的房間
+///
+/// 門算在連線（走廊）上，從任一側的房間都看得到
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloorRegion {
+    Room(usize),
+    Corridor(usize),  // RoomGraph.edges 的索引
 }
 
 #[derive(Component, Debug)]
@@ -52,7 +123,7 @@ pub struct RoomRect {
     pub height: usize,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct CompoundRoom {
     pub rectangles: Vec<RoomRect>,
     pub room_type: CompoundRoomType,
@@ -65,4 +136,88 @@ pub enum CompoundRoomType {
     TShape,     // T 形
     Plus,       // 十字形
     Custom,     // 自定義形狀
-}
\ No newline at end of file
+}
+
+/// 房間的哪一面牆
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum WallSide {
+    North,
+    South,
+    East,
+    West,
+}
+
+/// 門插槽 - 手工房間指定可以開門的位置（緊貼牆壁的地板格子與牆面方向）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoorSocket {
+    pub cell: IVec2,
+    pub side: WallSide,
+}
+
+impl DoorSocket {
+    /// 門所在的格子：南牆的門位於外側那一排（與 generate_room_tiles 的佈局一致）
+    pub fn door_cell(&self) -> IVec2 {
+        match self.side {
+            WallSide::North => self.cell + IVec2::Y,
+            WallSide::South => self.cell - IVec2::Y * 2,
+            WallSide::East => self.cell + IVec2::X,
+            WallSide::West => self.cell - IVec2::X,
+        }
+    }
+}
+
+impl RoomRect {
+    /// 可行走地板格子的範圍（格子座標，包含兩端），對應 generate_room_tiles 的佈局
+    pub fn floor_cells(&self) -> (IVec2, IVec2) {
+        let min = IVec2::new(self.x + 1, self.y + 1);
+        let max = IVec2::new(
+            self.x + self.width as i32 - 2,
+            self.y + self.height as i32 - 2,
+        );
+        (min, max)
+    }
+
+    /// 是否為此矩形的地板格子
+    pub fn contains_floor(&self, cell: IVec2) -> bool {
+        let (min, max) = self.floor_cells();
+        cell.cmpge(min).all() && cell.cmple(max).all()
+    }
+
+    /// 可行走地板瓷磚中心的世界座標範圍
+    pub fn floor_world_rect(&self, tile_size: f32) -> Rect {
+        let (min, max) = self.floor_cells();
+        Rect::new(
+            min.x as f32 * tile_size,
+            min.y as f32 * tile_size,
+            max.x as f32 * tile_size,
+            max.y as f32 * tile_size,
+        )
+    }
+}
+
+impl CompoundRoom {
+    /// 將所有矩形平移（格子座標）
+    pub fn translated(mut self, offset: IVec2) -> Self {
+        for rect in &mut self.rectangles {
+            rect.x += offset.x;
+            rect.y += offset.y;
+        }
+        self
+    }
+
+    /// 是否為房間任一矩形的地板格子
+    pub fn is_floor(&self, cell: IVec2) -> bool {
+        self.rectangles.iter().any(|rect| rect.contains_floor(cell))
+    }
+
+    /// 房間佔用範圍（含牆壁與南牆外側）的格子座標邊界，包含兩端
+    pub fn footprint_bounds(&self) -> (IVec2, IVec2) {
+        self.rectangles.iter().fold(
+            (IVec2::MAX, IVec2::MIN),
+            |(min, max), rect| (
+                min.min(IVec2::new(rect.x, rect.y - 1)),
+                max.max(IVec2::new(rect.x + rect.width as i32 - 1, rect.y + rect.height as i32 - 1)),
+            ),
+        )
+    }
+}
@@ -7,16 +7,107 @@ pub const GRID_SIZE: i32 = 20;
 // Room constants
 pub const ROOM_TILE_SIZE: f32 = 16.0;  // 房間瓷磚的實際大小
 
+// Floor generation constants
+pub const FLOOR_MIN_ROOMS: usize = 8;
+pub const FLOOR_MAX_ROOMS: usize = 20;
+pub const FLOOR_SLOT_SIZE: i32 = 32;  // 每個房間佔用的佈局格大小（瓷磚數），需大於最大房間尺寸
+pub const FLOOR_EXTRA_LOOP_CHANCE: f64 = 0.2;  // 生成樹以外的相鄰房間額外連通的機率
+pub const FLOOR_CUSTOM_ROOM_CHANCE: f64 = 0.35;  // 有手工房間樣板時，每個房間改用樣板的機率
+pub const FLOOR_LOCKED_DOOR_CHANCE: f64 = 0.5;  // 生成樹以外的連線（捷徑）改為上鎖門的機率
+
+// Room template constants
+pub const ROOM_TEMPLATE_DIR: &str = "rooms";  // assets 底下存放 *.room.ron 的資料夾
+pub const ROOM_TEMPLATE_MAX_SIZE: i32 = 24;   // 樣板含牆壁的最大寬高，需小於 FLOOR_SLOT_SIZE 以保留走廊空間
+pub const ROOM_TAG_START: &str = "start";     // 標記此標籤的樣板只會用作起始房間
+
+// Save constants
+pub const SAVE_FILE_PATH: &str = "saves/run.ron";  // 相對於工作目錄
+pub const SAVE_VERSION: u32 = 3;                   // 存檔格式改變時遞增，舊版本的存檔會被拒絕
+
 // Player constants
-pub const PLAYER_SPEED: f32 = 300.0;
+pub const PLAYER_SPEED: f32 = 200.0;
 pub const PLAYER_MAX_HEALTH: i32 = 100;
+pub const PLAYER_ATTACK_DAMAGE: i32 = 25;
+pub const PLAYER_ENTITY_SCALE: f32 = 4.0;  // 玩家實體的縮放（武器是子實體，會繼承這個縮放）
 pub const PLAYER_SCALE: f32 = 3.0;
+pub const PLAYER_COLLIDER_HALF_SIZE: f32 = 14.0;  // 玩家碰撞箱半寬（像素）
+pub const PLAYER_SPRITE_SIZE: f32 = 20.0;  // 玩家圖片在本地座標的大小（48px 的角色圖縮到這個大小，再乘上實體縮放）
+pub const DASH_SPEED: f32 = 800.0;    // 衝刺時的移動速度
+pub const DASH_DURATION: f32 = 0.15;  // 衝刺持續時間（秒）
+pub const DASH_COOLDOWN: f32 = 0.8;   // 兩次衝刺的最短間隔
+
+// Enemy constants
+pub const ENEMY_SCALE: f32 = 3.0;
+pub const ENEMIES_PER_ROOM_MIN: usize = 1;
+pub const ENEMIES_PER_ROOM_MAX: usize = 2;
+pub const ENEMY_PATROL_INTERVAL: f32 = 3.0;  // 巡邏換點的最長間隔（秒）
+pub const ENEMY_KNOCKBACK: f32 = 250.0;  // 敵人攻擊的擊退速度
+pub const ENEMY_ANIMATION_MIN_SPEED: f32 = 5.0;  // 速度超過此值才播放行走動畫
+
+// Hitbox constants
+pub const SWORD_HITBOX_REACH: f32 = 6.0;    // 判定中心在武器本地座標的距離
+pub const SWORD_HITBOX_RADIUS: f32 = 28.0;  // 劍的判定半徑（像素）
+pub const SWORD_KNOCKBACK: f32 = 400.0;     // 劍的擊退速度
+pub const SWORD_GRIP_OFFSET: f32 = 7.0;     // 劍的圖片中心沿面向離手的距離（玩家本地座標）
+pub const PLAYER_WEAPON_HAND: &str = "RIGHT ARM";  // 武器對齊的手部關鍵點
+pub const PLAYER_HURTBOX_RADIUS: f32 = 20.0;
+pub const PROJECTILE_WALL_HALF_SIZE: f32 = 4.0;  // 投射物撞牆用的碰撞箱半寬（像素）
+pub const PROJECTILE_HOMING_RANGE: f32 = 250.0;  // 追蹤型投射物尋找目標的距離
+pub const KNOCKBACK_DURATION: f32 = 0.15;
 
 // Camera constants
 pub const CAMERA_FOLLOW_SPEED: f32 = 3.0;
+pub const CAMERA_LOOK_AHEAD: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;  // 往面向的方向多看一格
+pub const CAMERA_DEAD_ZONE: (f32, f32) = (32.0, 24.0);  // 死區半寬與半高（像素）
+pub const CAMERA_ZOOM_SPEED: f32 = 4.0;
+pub const CAMERA_MIN_SCALE: f32 = 0.6;  // 小房間最多放大到這個縮放比例
+pub const CAMERA_PAN_TIME: f32 = 0.5;   // 換房間時鏡頭平移的秒數
+pub const CAMERA_SHAKE_MAX_OFFSET: f32 = 16.0;  // 強度 1 時的最大震動位移（像素）
+pub const CAMERA_SHAKE_MAX_ANGLE: f32 = 0.04;   // 強度 1 時的最大旋轉（弧度）
+pub const CAMERA_SHAKE_FREQUENCY: f32 = 30.0;
+pub const CAMERA_TRAUMA_DECAY: f32 = 1.5;       // 每秒減少的震動強度
+pub const CAMERA_KICK_DECAY: f32 = 12.0;        // 推動偏移的指數衰減速率
+
+// Game feel constants
+pub const HIT_STOP_DURATION: f32 = 0.06;   // 命中敵人時凍結的秒數
+pub const HIT_SHAKE_TRAUMA: f32 = 0.25;
+pub const HIT_KICK_STRENGTH: f32 = 8.0;    // 命中時相機往擊退方向推動的像素
+pub const PLAYER_HURT_TRAUMA: f32 = 0.5;
+pub const LEVEL_UP_TRAUMA: f32 = 0.3;  // 升級時的畫面震動
+pub const DOOR_SLAM_TRAUMA: f32 = 0.3;
+pub const PARRY_TRAUMA: f32 = 0.2;     // 完美格擋時的畫面震動
+
+// Timing attack constants
+pub const TIMING_ENGAGE_RANGE: f32 = 200.0;    // 可以開始時機攻擊的敵人距離
+pub const TIMING_WARMUP_DURATION: f32 = 1.0;   // 準備階段（敵人回合時是敵人的預備動作）
+pub const TIMING_ACTION_DURATION: f32 = 0.8;   // 可以按下攻擊/格擋的時間
+pub const TIMING_COOLDOWN_DURATION: f32 = 0.5; // 出手後顯示結果的時間
+pub const TIMING_CRITICAL_WINDOW: (f32, f32) = (0.3, 0.7);  // 行動階段中算作完美時機的區間
 
 // Input constants
 pub const INPUT_DEADZONE: f32 = 0.1;
+pub const INPUT_CONFIG_PATH: &str = "config/input.ron";  // 按鍵設定檔，相對於工作目錄
+
+// Interaction constants
+pub const DOOR_INTERACTION_RADIUS: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 1.5;  // 約一格半，站在門旁邊才能開門
+pub const INTERACTION_PROMPT_OFFSET: f32 = 36.0;  // 提示文字在物件上方的距離
+pub const INTERACTION_FACING_MIN_DOT: f32 = 0.5;  // 面向與物件方向的夾角需在 60 度以內
+pub const DOOR_SEAL_CLEARANCE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 1.5;  // 玩家離門這麼遠才封鎖房間
+pub const DOOR_AUTO_CLOSE_DISTANCE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 4.0;  // 玩家離開開啟的門超過 4 格自動關門
+
+// Item constants
+pub const ITEM_PICKUP_RADIUS: f32 = 40.0;  // 自動撿起的距離
+pub const ITEM_INTERACTION_RADIUS: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;  // 需要按互動鍵的物品（武器）的互動距離
+pub const ITEM_SIZE: f32 = 16.0;           // 地上物品的大小（像素）
+pub const ITEM_DROP_SCATTER: f32 = 12.0;   // 同一隻敵人掉落多個物品時彼此錯開的距離
+pub const ROOM_ITEM_CHANCE: f64 = 0.5;     // 每個房間放置一個藥水或金幣的機率
+pub const ROOM_GOLD_AMOUNT: (u32, u32) = (10, 30);
+pub const POTION_HEAL: i32 = 40;
+
+// Room transition constants
+pub const ROOM_TRANSITION_FADE_TIME: f32 = 0.2;  // 淡出與淡入各自的秒數
+pub const ROOM_TRANSITION_MIN_DOT: f32 = 0.5;    // 移動方向與穿過門的方向夾角需在 60 度以內
+pub const ROOM_TRANSITION_ARRIVAL_DISTANCE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 2.0;  // 抵達時站在相連的門內側兩格
 
 // Grid visual constants
 pub const GRID_DARK_COLOR: (f32, f32, f32) = (0.3, 0.3, 0.3);
@@ -25,7 +116,20 @@ pub const CENTER_MARKER_COLOR: (f32, f32, f32) = (0.8, 0.2, 0.2);
 pub const CORNER_MARKER_COLOR: (f32, f32, f32) = (0.2, 0.8, 0.2);
 pub const CORNER_MARKER_SCALE: f32 = 0.5;
 
+// UI constants
+pub const UI_TITLE_FONT_SIZE: f32 = 64.0;
+pub const UI_HINT_FONT_SIZE: f32 = 24.0;
+pub const UI_CONTROLS_FONT_SIZE: f32 = 18.0;
+pub const UI_PROMPT_FONT_SIZE: f32 = 16.0;
+pub const UI_HUD_FONT_SIZE: f32 = 20.0;
+pub const UI_TIMING_BAR_SIZE: (f32, f32) = (320.0, 16.0);  // 時機條的寬高（像素）
+pub const MENU_BACKGROUND_COLOR: (f32, f32, f32) = (0.08, 0.06, 0.1);
+pub const OVERLAY_ALPHA: f32 = 0.6;  // 暫停與結束畫面的背景透明度
+
 // Z-layer constants for proper rendering order
 pub const Z_LAYER_GRID: f32 = -1.0;
 pub const Z_LAYER_MARKERS: f32 = -0.5;
-pub const Z_LAYER_PLAYER: f32 = 0.0;
\ No newline at end of file
+pub const Z_LAYER_ITEM: f32 = -0.2;
+pub const Z_LAYER_PLAYER: f32 = 0.0;
+pub const Z_LAYER_PROJECTILE: f32 = 2.0;
+pub const Z_LAYER_PROMPT: f32 = 10.0;
\ No newline at end of file
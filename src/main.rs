@@ -5,6 +5,7 @@ mod components;
 mod resources;
 mod systems;
 mod plugins;
+mod states;
 
 use plugins::*;
 
@@ -12,15 +13,27 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest())) // 設定像素藝術使用點採樣
         .add_plugins((
+            GameStatePlugin,        // 遊戲流程狀態（主選單、暫停、結束畫面）
+            AssetLoadingPlugin,     // 資源載入與進度
+            SavePlugin,             // 存檔與繼續遊戲
             CameraPlugin,
             WorldPlugin,
-            // PlayerPlugin,        // 暫時禁用舊的 PlayerPlugin
+            PlayerPlugin,           // 玩家生成與移動
             InputPlugin,
-            // AttackPlugin,        // 暫時禁用舊的 AttackPlugin
-            VisualCombatPlugin,     // 使用新的視覺化戰鬥系統
+            VisualCombatPlugin,     // 武器跟隨與揮擊
+            InteractablePlugin,     // 互動目標與提示
             DoorInteractionPlugin,  // 門交互系統
             WallCollisionPlugin,    // 牆壁碰撞檢測系統
             RoomTransitionPlugin,   // 房間切換系統
+            EnemyPlugin,            // 敵人 AI 與戰鬥
+            HitboxPlugin,           // 命中判定與傷害
+            // 外掛數量超過元組上限，其餘的放在第二組
+            (
+                CharacterAnimationPlugin,  // 角色動畫狀態機
+                InventoryPlugin,           // 物品欄與撿拾
+                AttackPlugin,              // 時機攻擊模式
+                StatusEffectsPlugin,       // 狀態效果
+            ),
         ))
         .run();
 }
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use crate::systems::animation::{
+    player_animation_state_system,
+    enemy_animation_state_system,
+    animate_characters_system,
+};
+use crate::systems::visual_combat::update_weapon_swing_animation_system;
+use crate::systems::enemy::enemy_ai_system;
+use crate::systems::hitbox::apply_damage_system;
+use crate::states::AppState;
+
+pub struct CharacterAnimationPlugin;
+
+impl Plugin for CharacterAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // 移動、揮擊與傷害都結算後才決定這一幀的動畫
+            .add_systems(Update, (
+                (
+                    player_animation_state_system.after(update_weapon_swing_animation_system),
+                    enemy_animation_state_system.after(enemy_ai_system),
+                ).after(apply_damage_system),
+                animate_characters_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
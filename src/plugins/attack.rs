@@ -1,28 +1,29 @@
 use bevy::prelude::*;
 use crate::systems::attack::{
-    attack_input_system, handle_attack_events, update_melee_attacks, 
-    update_attack_animations, update_weapon_effects, update_projectiles, 
-    weapon_switching_system, combat_state_system, timing_attack_system
+    combat_state_system, weapon_selection_system, timing_attack_system, enemy_timing_attack_system,
+    end_timing_combat_system, spawn_timing_bar_system, update_timing_bar_system,
 };
-use crate::components::attack::{AttackEvent, CombatState};
+use crate::systems::hitbox::apply_damage_system;
+use crate::components::attack::CombatState;
+use crate::states::AppState;
 
+/// 時機攻擊模式 - 與一個敵人輪流出手，按鍵時機決定造成與受到的傷害
 pub struct AttackPlugin;
 
 impl Plugin for AttackPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_state::<CombatState>() // 新增戰鬥狀態
-            .add_event::<AttackEvent>()
+            .add_sub_state::<CombatState>() // 新增戰鬥狀態
+            .add_systems(OnEnter(AppState::Loading), spawn_timing_bar_system)
+            .add_systems(OnEnter(CombatState::Idle), end_timing_combat_system)
             .add_systems(Update, (
-                weapon_switching_system,
-                combat_state_system,     // 新增戰鬥狀態管理
-                timing_attack_system,    // 新增時機攻擊系統
-                attack_input_system,
-                handle_attack_events,
-                update_melee_attacks,
-                update_attack_animations,
-                update_weapon_effects,
-                update_projectiles,
-            ).chain()); // chain() 確保系統按順序執行
+                combat_state_system.run_if(in_state(CombatState::Idle)),
+                weapon_selection_system.run_if(in_state(CombatState::PlayerSelecting)),
+                timing_attack_system.run_if(in_state(CombatState::PlayerAttacking)),
+                enemy_timing_attack_system.run_if(in_state(CombatState::EnemyAttacking)),
+                update_timing_bar_system,
+            ).chain() // chain() 確保系統按順序執行
+                .before(apply_damage_system)
+                .run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
+use crate::components::camera::CameraImpulseEvent;
 use crate::systems::{
     setup::setup,
-    camera::camera_follow_system,
+    camera::{camera_follow_system, start_camera_pan_system, camera_effects_system},
+    dungeon::track_current_room_system,
 };
 
 pub struct CameraPlugin;
@@ -9,7 +11,13 @@ pub struct CameraPlugin;
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<CameraImpulseEvent>()
             .add_systems(Startup, setup)
-            .add_systems(Update, camera_follow_system);
+            // 效果偏移在跟隨之後疊加；不限定狀態，離開遊戲時凍結也會照常結束
+            .add_systems(Update, (
+                start_camera_pan_system.after(track_current_room_system),
+                camera_follow_system,
+                camera_effects_system,
+            ).chain());
     }
-}
\ No newline at end of file
+}
@@ -1,17 +1,35 @@
 use bevy::prelude::*;
-use crate::systems::door_interaction::{door_interaction_system, auto_close_door_system, door_collision_system};
-use crate::systems::input::DoorInteractionEvent;
+use crate::systems::door_interaction::{
+    assign_doors_system,
+    door_interaction_system,
+    seal_room_doors_system,
+    grant_key_system,
+    auto_close_door_system,
+    sync_door_prompt_system,
+};
+use crate::systems::dungeon::{spawn_dungeon_floor, track_current_room_system, update_cleared_rooms_system};
+use crate::systems::interaction::interact_input_system;
+use crate::resources::RoomGraph;
+use crate::states::AppState;
 
 pub struct DoorInteractionPlugin;
 
 impl Plugin for DoorInteractionPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_event::<DoorInteractionEvent>()
+            // 樓層生成後標記門所屬的房間與上鎖的門
+            .add_systems(Update, assign_doors_system
+                .after(spawn_dungeon_floor)
+                .run_if(resource_added::<RoomGraph>))
             .add_systems(Update, (
-                door_interaction_system,
-                door_collision_system,
-                // auto_close_door_system, // 暫時禁用自動關門
-            ));
+                door_interaction_system.after(interact_input_system),
+                grant_key_system,
+                seal_room_doors_system
+                    .after(track_current_room_system)
+                    .after(update_cleared_rooms_system),
+                // 關閉的門由 TileCollisionMap 阻擋
+                auto_close_door_system,
+                sync_door_prompt_system,
+            ).chain().run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
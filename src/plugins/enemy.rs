@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use crate::systems::enemy::{
+    spawn_enemies_system,
+    enemy_ai_system,
+    enemy_movement_system,
+    enemy_attack_system,
+    enemy_death_system,
+    EnemyDefeatedEvent,
+};
+use crate::systems::dungeon::spawn_dungeon_floor;
+use crate::systems::hitbox::apply_damage_system;
+use crate::components::attack::CombatState;
+use crate::resources::RoomGraph;
+use crate::states::AppState;
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<EnemyDefeatedEvent>()
+            // 房間生成後才能在房間內放置敵人
+            .add_systems(Update, spawn_enemies_system
+                .after(spawn_dungeon_floor)
+                .run_if(resource_added::<RoomGraph>))
+            // 時機攻擊模式中敵人改由回合出手
+            .add_systems(Update, (
+                enemy_ai_system,
+                enemy_movement_system,
+                enemy_attack_system.before(apply_damage_system),
+            ).chain()
+                .run_if(in_state(AppState::Playing))
+                .run_if(in_state(CombatState::Idle)))
+            // 傷害結算後才判定死亡
+            .add_systems(Update, enemy_death_system
+                .after(apply_damage_system)
+                .run_if(in_state(AppState::Playing)));
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use crate::systems::hitbox::{swing_hitbox_system, apply_damage_system, hit_feedback_system, knockback_system};
+use crate::systems::visual_combat::update_weapon_swing_animation_system;
+use crate::components::attack::DamageEvent;
+use crate::states::AppState;
+
+pub struct HitboxPlugin;
+
+impl Plugin for HitboxPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<DamageEvent>()
+            .add_systems(Update, (
+                // 判定框要在揮擊角度更新後才計算位置
+                swing_hitbox_system.after(update_weapon_swing_animation_system),
+                apply_damage_system,
+                hit_feedback_system,
+                knockback_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
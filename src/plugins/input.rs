@@ -1,12 +1,44 @@
 use bevy::prelude::*;
-use crate::systems::input::{input_system, AttackInputEvent};
+use bevy::input::InputSystem;
+use std::path::Path;
+use crate::systems::input::{
+    input_system,
+    update_action_state_system,
+    spawn_controls_panel_system,
+    rebind_input_system,
+    update_controls_text_system,
+    cancel_rebind_system,
+    AttackInputEvent,
+};
+use crate::systems::state::toggle_pause_system;
+use crate::systems::movement::player_movement_system;
+use crate::components::attack::CombatState;
+use crate::resources::{ActionState, InputBindings, PendingRebind};
+use crate::states::AppState;
+use crate::constants::*;
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app
+            .insert_resource(InputBindings::load_or_default(Path::new(INPUT_CONFIG_PATH)))
+            .init_resource::<ActionState>()
+            .init_resource::<PendingRebind>()
             .add_event::<AttackInputEvent>()
-            .add_systems(Update, input_system);
+            // 動作狀態在所有狀態下都更新（暫停畫面也需要讀取暫停動作）
+            .add_systems(PreUpdate, update_action_state_system.after(InputSystem))
+            // 時機攻擊模式直接讀取動作狀態
+            .add_systems(Update, input_system
+                .before(player_movement_system)
+                .run_if(in_state(AppState::Playing))
+                .run_if(in_state(CombatState::Idle)))
+            // 暫停畫面的按鍵設定
+            .add_systems(OnEnter(AppState::Paused), spawn_controls_panel_system)
+            .add_systems(OnExit(AppState::Paused), cancel_rebind_system)
+            .add_systems(Update, (
+                rebind_input_system.after(toggle_pause_system),
+                update_controls_text_system,
+            ).chain().run_if(in_state(AppState::Paused)));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use crate::systems::interaction::{
+    find_interaction_target_system,
+    interact_input_system,
+    spawn_interaction_prompt_system,
+    update_interaction_prompt_system,
+};
+use crate::components::interaction::InteractEvent;
+use crate::resources::InteractionTarget;
+use crate::states::AppState;
+
+pub struct InteractablePlugin;
+
+impl Plugin for InteractablePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<InteractionTarget>()
+            .add_event::<InteractEvent>()
+            .add_systems(OnEnter(AppState::Loading), spawn_interaction_prompt_system)
+            .add_systems(Update, (
+                find_interaction_target_system,
+                interact_input_system,
+                update_interaction_prompt_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use crate::systems::inventory::{
+    spawn_room_items_system,
+    drop_enemy_loot_system,
+    collect_pickups_system,
+    use_item_system,
+    spawn_inventory_hud_system,
+    update_inventory_hud_system,
+};
+use crate::systems::dungeon::spawn_dungeon_floor;
+use crate::systems::enemy::enemy_death_system;
+use crate::systems::interaction::interact_input_system;
+use crate::components::inventory::InventoryEvent;
+use crate::resources::{RoomGraph, CollectedItems};
+use crate::states::AppState;
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<InventoryEvent>()
+            .init_resource::<CollectedItems>()
+            // 樓層生成後在房間內放置物品
+            .add_systems(Update, spawn_room_items_system
+                .after(spawn_dungeon_floor)
+                .run_if(resource_added::<RoomGraph>))
+            .add_systems(OnEnter(AppState::Loading), spawn_inventory_hud_system)
+            .add_systems(Update, (
+                drop_enemy_loot_system.after(enemy_death_system),
+                collect_pickups_system.after(interact_input_system),
+                use_item_system,
+                update_inventory_hud_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use crate::systems::loading::{load_game_assets_system, spawn_loading_screen_system, loading_progress_system};
+use crate::resources::{CharacterMetadata, CharacterMetadataLoader};
+use crate::states::AppState;
+
+pub struct AssetLoadingPlugin;
+
+impl Plugin for AssetLoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<CharacterMetadata>()
+            .init_asset_loader::<CharacterMetadataLoader>()
+            .add_systems(Startup, load_game_assets_system)
+            .add_systems(OnEnter(AppState::Loading), spawn_loading_screen_system)
+            .add_systems(Update, loading_progress_system.run_if(in_state(AppState::Loading)));
+    }
+}
@@ -8,6 +8,15 @@ pub mod visual_combat;  // 新的視覺化戰鬥插件
 pub mod door_interaction;  // 門交互插件
 pub mod wall_collision;  // 牆壁碰撞插件
 pub mod room_transition;  // 房間切換插件
+pub mod enemy;  // 敵人插件
+pub mod hitbox;  // 命中判定插件
+pub mod state;  // 遊戲流程狀態插件
+pub mod loading;  // 資源載入插件
+pub mod save;  // 存檔插件
+pub mod interaction;  // 互動插件
+pub mod animation;  // 角色動畫插件
+pub mod inventory;  // 物品欄與撿拾插件
+pub mod status;  // 狀態效果插件
 
 // Re-export all plugins for easy importing
 pub use attack::*;
@@ -18,4 +27,13 @@ pub use input::*;
 pub use visual_combat::*;
 pub use door_interaction::*;
 pub use wall_collision::*;
-pub use room_transition::*;
\ No newline at end of file
+pub use room_transition::*;
+pub use enemy::*;
+pub use hitbox::*;
+pub use state::*;
+pub use loading::*;
+pub use save::*;
+pub use interaction::*;
+pub use animation::*;
+pub use inventory::*;
+pub use status::*;
\ No newline at end of file
@@ -1,16 +1,40 @@
 use bevy::prelude::*;
 use crate::systems::{
-    setup::{load_sprite_handles, spawn_player},
-    movement::{movement_system, sprite_direction_system},
-    health::health_system,
+    setup::spawn_player,
+    movement::{player_dash_system, player_movement_system, update_player_facing_system},
+    dungeon::spawn_dungeon_floor,
+    enemy::enemy_death_system,
+    progression::{gain_experience_system, apply_level_stats_system, level_up_feedback_system, LevelUpEvent},
 };
+use crate::components::attack::CombatState;
+use crate::resources::{PlayerArchetype, RoomGraph};
+use crate::states::AppState;
 
+/// 玩家插件 - 依 PlayerArchetype 生成玩家，負責唯一的移動流程與升級
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Startup, (load_sprite_handles, spawn_player).chain())
-            .add_systems(Update, (movement_system, sprite_direction_system, health_system));
+            .init_resource::<PlayerArchetype>()
+            .add_event::<LevelUpEvent>()
+            // 樓層生成後，在起始房間生成玩家和武器
+            .add_systems(Update, spawn_player
+                .after(spawn_dungeon_floor)
+                .run_if(resource_added::<RoomGraph>))
+            .add_systems(Update, (
+                // 時機攻擊模式中玩家站定不動
+                player_dash_system.run_if(in_state(CombatState::Idle)),
+                player_movement_system.run_if(in_state(CombatState::Idle)),
+                update_player_facing_system,
+            ).chain()
+                .run_if(in_state(AppState::Playing)))
+            // 擊倒敵人後結算經驗值，升級後套用新的能力值
+            .add_systems(Update, (
+                gain_experience_system.after(enemy_death_system),
+                apply_level_stats_system,
+                level_up_feedback_system,
+            ).chain()
+                .run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
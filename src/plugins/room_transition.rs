@@ -1,12 +1,27 @@
 use bevy::prelude::*;
-use crate::systems::room_transition::{room_transition_system, TransitionCooldown};
+use crate::systems::room_transition::{
+    spawn_screen_fade_system,
+    start_room_transition_system,
+    update_room_transition_system,
+    update_room_visibility_system,
+    RoomTransition,
+};
+use crate::systems::dungeon::track_current_room_system;
+use crate::states::AppState;
 
 pub struct RoomTransitionPlugin;
 
 impl Plugin for RoomTransitionPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<TransitionCooldown>()
-            .add_systems(Update, room_transition_system);
+            .init_resource::<RoomTransition>()
+            .add_systems(OnEnter(AppState::Loading), spawn_screen_fade_system)
+            // 玩家移到新房間後才更新目前房間，同一幀送出 RoomEnteredEvent 並切換顯示的房間
+            .add_systems(Update, (
+                (start_room_transition_system, update_room_transition_system)
+                    .chain()
+                    .before(track_current_room_system),
+                update_room_visibility_system.after(track_current_room_system),
+            ).run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
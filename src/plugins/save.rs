@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use crate::systems::save::{
+    save_run_system,
+    save_and_quit_system,
+    continue_run_input_system,
+    restore_run_system,
+    delete_save_system,
+    SaveRunEvent,
+};
+use crate::systems::input::rebind_input_system;
+use crate::resources::PendingRestore;
+use crate::states::AppState;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<SaveRunEvent>()
+            .add_systems(OnEnter(AppState::Playing), restore_run_system.run_if(resource_exists::<PendingRestore>))
+            .add_systems(OnEnter(AppState::GameOver), delete_save_system)
+            .add_systems(OnEnter(AppState::Victory), delete_save_system)
+            .add_systems(Update, (
+                continue_run_input_system.run_if(in_state(AppState::MainMenu)),
+                // 在按鍵設定之前，才不會把剛綁定的按鍵當成存檔並離開
+                save_and_quit_system.before(rebind_input_system).run_if(in_state(AppState::Paused)),
+            ))
+            // 放在 Last，才能收到同一幀送出的檢查點與關閉遊戲事件
+            .add_systems(Last, save_run_system
+                .run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))));
+    }
+}
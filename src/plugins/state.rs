@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use crate::systems::state::{
+    reset_run_system,
+    spawn_main_menu_system,
+    spawn_pause_screen_system,
+    spawn_game_over_screen_system,
+    spawn_victory_screen_system,
+    main_menu_input_system,
+    finish_loading_system,
+    toggle_pause_system,
+    victory_check_system,
+    end_screen_input_system,
+};
+use crate::systems::health::health_system;
+use crate::systems::enemy::enemy_death_system;
+use crate::systems::hitbox::apply_damage_system;
+use crate::resources::RoomGraph;
+use crate::states::{AppState, InRun};
+
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_state::<AppState>()
+            .add_computed_state::<InRun>()
+            .enable_state_scoped_entities::<AppState>()
+            .enable_state_scoped_entities::<InRun>()
+            // 畫面
+            .add_systems(OnEnter(AppState::MainMenu), (reset_run_system, spawn_main_menu_system))
+            .add_systems(OnEnter(AppState::Paused), spawn_pause_screen_system)
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen_system)
+            .add_systems(OnEnter(AppState::Victory), spawn_victory_screen_system)
+            // 狀態切換
+            .add_systems(Update, (
+                main_menu_input_system.run_if(in_state(AppState::MainMenu)),
+                finish_loading_system.run_if(in_state(AppState::Loading).and(resource_exists::<RoomGraph>)),
+                toggle_pause_system.run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))),
+                (health_system.after(apply_damage_system), victory_check_system.after(enemy_death_system))
+                    .run_if(in_state(AppState::Playing)),
+                end_screen_input_system.run_if(in_state(AppState::GameOver).or(in_state(AppState::Victory))),
+            ));
+    }
+}
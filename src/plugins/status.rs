@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use crate::systems::status::{apply_status_system, update_status_effects_system, status_tint_system};
+use crate::systems::hitbox::apply_damage_system;
+use crate::components::status::ApplyStatusEvent;
+use crate::states::AppState;
+
+/// 狀態效果 - 燃燒、中毒、緩速、暈眩、再生
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ApplyStatusEvent>()
+            .add_systems(Update, (
+                apply_status_system,
+                update_status_effects_system.before(apply_damage_system),
+                status_tint_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
@@ -1,29 +1,43 @@
 use bevy::prelude::*;
 use crate::systems::visual_combat::{
-    player_input_system,
-    player_movement_system,
-    update_player_facing_system,
     update_weapon_offset_system,
     visual_attack_input_system,
     update_weapon_swing_animation_system,
-    spawn_player_with_weapon_system,
+    switch_weapon_system,
+    equip_current_weapon_system,
 };
+use crate::systems::projectile::{projectile_movement_system, projectile_hit_system, update_impact_effects_system};
+use crate::systems::movement::update_player_facing_system;
+use crate::systems::hitbox::apply_damage_system;
+use crate::components::attack::CombatState;
+use crate::resources::WeaponCatalog;
+use crate::states::AppState;
 
 pub struct VisualCombatPlugin;
 
 impl Plugin for VisualCombatPlugin {
     fn build(&self, app: &mut App) {
         app
-            // 在遊戲開始時生成玩家和武器
-            .add_systems(Startup, spawn_player_with_weapon_system)
-            // 更新系統
+            // 載入武器圖片時需要武器設定表
+            .init_resource::<WeaponCatalog>()
+            // 武器跟著玩家的面向與揮擊更新
+            // 時機攻擊模式中由武器選擇與時機攻擊系統負責切換與出手
             .add_systems(Update, (
-                player_input_system,
-                player_movement_system,
-                update_player_facing_system,
+                switch_weapon_system.run_if(in_state(CombatState::Idle)),
+                equip_current_weapon_system,
                 update_weapon_offset_system,
-                visual_attack_input_system,
+                visual_attack_input_system.run_if(in_state(CombatState::Idle)),
                 update_weapon_swing_animation_system,
-            ).chain()); // 確保系統按順序執行
+            ).chain() // 確保系統按順序執行
+                .after(update_player_facing_system)
+                .run_if(in_state(AppState::Playing)))
+            // 投射物在扣血前完成命中判定
+            .add_systems(Update, (
+                projectile_movement_system,
+                projectile_hit_system,
+                update_impact_effects_system,
+            ).chain()
+                .before(apply_damage_system)
+                .run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,15 @@
 use bevy::prelude::*;
-use crate::systems::wall_collision::simple_wall_collision_system;
+use crate::systems::wall_collision::update_collision_map_system;
+use crate::resources::TileCollisionMap;
+use crate::states::AppState;
 
 pub struct WallCollisionPlugin;
 
 impl Plugin for WallCollisionPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, simple_wall_collision_system);
+            .init_resource::<TileCollisionMap>()
+            // 在 Update 的移動系統之前更新碰撞表
+            .add_systems(PreUpdate, update_collision_map_system.run_if(in_state(AppState::Playing)));
     }
 }
\ No newline at end of file
@@ -1,10 +1,47 @@
 use bevy::prelude::*;
-use crate::systems::world::{spawn_grid, spawn_room};
+use crate::systems::world::spawn_grid;
+use crate::systems::loading::assets_loaded;
+use crate::systems::dungeon::{
+    floor_ready_to_generate,
+    spawn_dungeon_floor,
+    track_current_room_system,
+    update_cleared_rooms_system,
+    RoomClearedEvent,
+    RoomEnteredEvent,
+};
+use crate::systems::enemy::enemy_death_system;
+use crate::states::AppState;
+use crate::resources::{CurrentRoom, ClearedRooms, DungeonSeed, PinnedSeed, GameRng, RngStream, RoomTemplate, RoomTemplateLoader};
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (spawn_grid, spawn_room).chain());
+        // 已經指定種子（例如測試）就沿用，否則讀取命令列參數或環境變數；都沒有時每一局重新隨機
+        let pinned = PinnedSeed(app.world().get_resource::<DungeonSeed>().copied()
+            .or_else(DungeonSeed::from_args_or_env));
+        let seed = pinned.next_run_seed();
+
+        app
+            .insert_resource(pinned)
+            .insert_resource(seed)
+            .insert_resource(GameRng(seed.rng(RngStream::Runtime)))
+            .init_resource::<CurrentRoom>()
+            .init_resource::<ClearedRooms>()
+            .add_event::<RoomClearedEvent>()
+            .add_event::<RoomEnteredEvent>()
+            .init_asset::<RoomTemplate>()
+            .init_asset_loader::<RoomTemplateLoader>()
+            .add_systems(Startup, spawn_grid)
+            // 進入 Loading 且必要資源與手工房間樣板都載入完成後才生成樓層
+            .add_systems(Update, (
+                spawn_dungeon_floor.run_if(
+                    in_state(AppState::Loading).and(assets_loaded).and(floor_ready_to_generate),
+                ),
+                track_current_room_system.run_if(in_state(AppState::Playing)),
+            ).chain())
+            .add_systems(Update, update_cleared_rooms_system
+                .after(enemy_death_system)
+                .run_if(in_state(AppState::Playing)));
     }
-}
\ No newline at end of file
+}
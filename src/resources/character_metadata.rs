@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, LoadDirectError};
+use bevy::asset::io::Reader;
+use bevy::image::{TextureAtlasBuilder, TextureAtlasBuilderError};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use crate::components::animation::AnimationKind;
+use crate::components::movement::Direction;
+
+/// 角色描述檔 - 從 assets/characters/<角色>/metadata.json 載入
+///
+/// 所有靜止圖與動畫影格在載入時打包成一張圖集（子資源 `#atlas` 與 `#layout`），
+/// 描述檔載入完成代表圖集也準備好了。檔案中沒用到的欄位（角色名稱、關鍵點深度等）會被忽略
+#[derive(Asset, TypePath, Debug)]
+pub struct CharacterMetadata {
+    pub size: UVec2,                                // 單張圖片的像素尺寸
+    pub keypoints: HashMap<Direction, Vec<Keypoint>>,  // 各方向靜止圖的骨架關鍵點
+    pub atlas: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub stills: HashMap<Direction, usize>,                        // 各方向靜止圖在圖集中的索引
+    pub clips: HashMap<(AnimationKind, Direction), Vec<usize>>,   // 各動畫各方向的影格索引
+    pub clip_keypoints: HashMap<(AnimationKind, Direction), Vec<Vec<Keypoint>>>,  // 各動畫影格的骨架關鍵點（與 clips 對齊）
+}
+
+/// 骨架關鍵點（圖片像素座標，原點在左上角）
+#[derive(Debug, Clone, Deserialize)]
+pub struct Keypoint {
+    pub label: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl CharacterMetadata {
+    /// 動畫的影格索引；沒有這個動畫時依序退回待機動畫、該方向的靜止圖、朝南的靜止圖
+    pub fn frames(&self, kind: AnimationKind, direction: Direction) -> &[usize] {
+        self.clips.get(&(kind, direction))
+            .or_else(|| self.clips.get(&(AnimationKind::Idle, direction)))
+            .map(Vec::as_slice)
+            .or_else(|| self.stills.get(&direction).map(std::slice::from_ref))
+            .or_else(|| self.stills.get(&Direction::South).map(std::slice::from_ref))
+            .unwrap_or_default()
+    }
+
+    /// 指定動畫影格上的關鍵點，以圖片中心為原點、y 軸向上（像素）
+    ///
+    /// 依照和 `frames` 相同的順序退回；動畫影格沒有關鍵點時使用該方向靜止圖的關鍵點
+    pub fn keypoint(&self, kind: AnimationKind, direction: Direction, frame: usize, label: &str) -> Option<Vec2> {
+        let clip = [(kind, direction), (AnimationKind::Idle, direction)].into_iter()
+            .find(|key| self.clips.contains_key(key));
+        let points = clip
+            .and_then(|key| self.clip_keypoints.get(&key))
+            .and_then(|frames| frames.get(frame.min(frames.len().saturating_sub(1))))
+            .or_else(|| self.keypoints.get(&direction))
+            .or_else(|| self.keypoints.get(&Direction::South))?;
+
+        let point = points.iter().find(|point| point.label == label)?;
+        let half = self.size.as_vec2() / 2.0;
+        Some(Vec2::new(point.x - half.x, half.y - point.y))
+    }
+
+    /// 以圖集建立指定方向的靜止 Sprite
+    pub fn sprite(&self, direction: Direction) -> Sprite {
+        let index = self.frames(AnimationKind::Idle, direction).first().copied().unwrap_or_default();
+        Sprite::from_atlas_image(self.atlas.clone(), TextureAtlas { layout: self.layout.clone(), index })
+    }
+}
+
+// ---- 檔案格式 ----
+
+#[derive(Deserialize)]
+struct MetadataFile {
+    character: CharacterDef,
+    frames: FramesDef,
+    #[serde(default)]
+    keypoints: KeypointsDef,
+}
+
+#[derive(Deserialize)]
+struct CharacterDef {
+    size: SizeDef,
+}
+
+#[derive(Deserialize)]
+struct SizeDef {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Deserialize)]
+struct FramesDef {
+    rotations: BTreeMap<String, String>,  // 方向名稱 -> 相對於描述檔的圖片路徑
+    /// 動畫名稱 -> 方向名稱 -> 依序的影格圖片路徑
+    #[serde(default)]
+    animations: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Deserialize, Default)]
+struct KeypointsDef {
+    #[serde(default)]
+    rotations: HashMap<String, Vec<Keypoint>>,
+    /// 動畫名稱 -> 方向名稱 -> 每一格的關鍵點
+    #[serde(default)]
+    animations: HashMap<String, HashMap<String, Vec<Vec<Keypoint>>>>,
+}
+
+/// 角色描述檔載入錯誤
+#[derive(Debug)]
+pub enum CharacterMetadataError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Frame(Box<LoadDirectError>),
+    Atlas(TextureAtlasBuilderError),
+}
+
+impl fmt::Display for CharacterMetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharacterMetadataError::Io(error) => write!(f, "無法讀取角色描述檔: {error}"),
+            CharacterMetadataError::Parse(error) => write!(f, "角色描述檔格式錯誤: {error}"),
+            CharacterMetadataError::Frame(error) => write!(f, "無法載入角色影格: {error}"),
+            CharacterMetadataError::Atlas(error) => write!(f, "無法建立角色圖集: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CharacterMetadataError {}
+
+impl From<std::io::Error> for CharacterMetadataError {
+    fn from(error: std::io::Error) -> Self {
+        CharacterMetadataError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for CharacterMetadataError {
+    fn from(error: serde_json::Error) -> Self {
+        CharacterMetadataError::Parse(error)
+    }
+}
+
+impl From<LoadDirectError> for CharacterMetadataError {
+    fn from(error: LoadDirectError) -> Self {
+        CharacterMetadataError::Frame(Box::new(error))
+    }
+}
+
+impl From<TextureAtlasBuilderError> for CharacterMetadataError {
+    fn from(error: TextureAtlasBuilderError) -> Self {
+        CharacterMetadataError::Atlas(error)
+    }
+}
+
+#[derive(Default)]
+pub struct CharacterMetadataLoader;
+
+impl AssetLoader for CharacterMetadataLoader {
+    type Asset = CharacterMetadata;
+    type Settings = ();
+    type Error = CharacterMetadataError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<CharacterMetadata, CharacterMetadataError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: MetadataFile = serde_json::from_slice(&bytes)?;
+
+        // 圖片路徑相對於描述檔所在的資料夾
+        let directory = load_context.path().parent().map(|path| path.to_path_buf()).unwrap_or_default();
+
+        // 立即讀取所有影格圖片，依檔案中的順序打包成圖集（圖集索引等於加入的順序）
+        let paths = frame_paths(&file.frames);
+        let mut images = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let image = load_context.loader().immediate().load::<Image>(directory.join(path)).await?;
+            images.push(image.take());
+        }
+        let mut builder = TextureAtlasBuilder::default();
+        for image in &images {
+            builder.add_texture(None, image);
+        }
+        let (layout, _, atlas_image) = builder.build()?;
+
+        let index_of = |path: &String| paths.iter().position(|candidate| candidate == path).unwrap_or_default();
+        let stills = file.frames.rotations.iter()
+            .filter_map(|(direction, path)| Some((Direction::from_name(direction)?, index_of(path))))
+            .collect();
+        let keypoints = file.keypoints.rotations.into_iter()
+            .filter_map(|(direction, points)| Some((Direction::from_name(&direction)?, points)))
+            .collect();
+        let mut clips = HashMap::new();
+        let mut clip_keypoints = HashMap::new();
+        for (name, directions) in &file.frames.animations {
+            let Some(kind) = AnimationKind::from_name(name) else {
+                warn!("{}：無法判斷動畫 {} 的種類，略過", load_context.path().display(), name);
+                continue;
+            };
+            for (direction_name, frames) in directions {
+                let Some(direction) = Direction::from_name(direction_name) else {
+                    continue;
+                };
+                // 同一種類有多個動畫時使用名稱排序最前面的
+                if clips.contains_key(&(kind, direction)) {
+                    continue;
+                }
+                clips.insert((kind, direction), frames.iter().map(index_of).collect());
+                if let Some(points) = file.keypoints.animations.get(name).and_then(|directions| directions.get(direction_name)) {
+                    clip_keypoints.insert((kind, direction), points.clone());
+                }
+            }
+        }
+
+        Ok(CharacterMetadata {
+            size: UVec2::new(file.character.size.width, file.character.size.height),
+            keypoints,
+            atlas: load_context.add_labeled_asset("atlas".to_string(), atlas_image),
+            layout: load_context.add_labeled_asset("layout".to_string(), layout),
+            stills,
+            clips,
+            clip_keypoints,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+/// 圖集要包含的所有圖片（不重複，靜止圖在前，動畫依名稱與方向排序）
+fn frame_paths(frames: &FramesDef) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    frames.rotations.values()
+        .chain(frames.animations.values().flat_map(|directions| directions.values().flatten()))
+        .filter(|path| seen.insert(path.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::CharacterMetadataHandles;
+
+    #[test]
+    fn bundled_metadata_files_parse() {
+        for name in CharacterMetadataHandles::CHARACTERS {
+            let path = format!("assets/characters/{name}/metadata.json");
+            let bytes = std::fs::read(&path).unwrap();
+            let file: MetadataFile = serde_json::from_slice(&bytes)
+                .unwrap_or_else(|error| panic!("{path}: {error}"));
+
+            assert!(file.character.size.width > 0 && file.character.size.height > 0);
+            assert!(file.frames.rotations.contains_key("south"), "{path} 缺少 south 方向");
+            for image in frame_paths(&file.frames) {
+                assert!(std::path::Path::new(&format!("assets/characters/{name}/{image}")).exists(), "{path} 引用了不存在的 {image}");
+            }
+        }
+    }
+
+    #[test]
+    fn animation_frames_are_deduplicated_in_file_order() {
+        let frames: FramesDef = serde_json::from_str(r#"{
+            "rotations": { "south": "rotations/south.png", "east": "rotations/east.png" },
+            "animations": {
+                "walking-4-frames": { "south": ["walk/s0.png", "walk/s1.png"], "east": ["walk/e0.png"] },
+                "breathing-idle": { "south": ["rotations/south.png", "idle/s1.png"] }
+            }
+        }"#).unwrap();
+
+        assert_eq!(
+            frame_paths(&frames),
+            ["rotations/east.png", "rotations/south.png", "idle/s1.png", "walk/e0.png", "walk/s0.png", "walk/s1.png"],
+        );
+    }
+
+    #[test]
+    fn animation_names_map_to_kinds() {
+        assert_eq!(AnimationKind::from_name("breathing-idle"), Some(AnimationKind::Idle));
+        assert_eq!(AnimationKind::from_name("walking-8-frames"), Some(AnimationKind::Walk));
+        assert_eq!(AnimationKind::from_name("cross-punch"), Some(AnimationKind::Attack));
+        assert_eq!(AnimationKind::from_name("taking-punch"), Some(AnimationKind::Hurt));
+        assert_eq!(AnimationKind::from_name("falling-back-death"), Some(AnimationKind::Death));
+        assert_eq!(AnimationKind::from_name("jumping"), None);
+    }
+
+    #[test]
+    fn missing_clips_fall_back_to_idle_then_stills() {
+        let metadata = CharacterMetadata {
+            size: UVec2::splat(48),
+            keypoints: HashMap::new(),
+            atlas: Handle::default(),
+            layout: Handle::default(),
+            stills: HashMap::from([(Direction::South, 0), (Direction::East, 1)]),
+            clips: HashMap::from([
+                ((AnimationKind::Walk, Direction::East), vec![4, 5, 6]),
+                ((AnimationKind::Idle, Direction::East), vec![2, 3]),
+            ]),
+            clip_keypoints: HashMap::new(),
+        };
+
+        assert_eq!(metadata.frames(AnimationKind::Walk, Direction::East), &[4, 5, 6]);
+        assert_eq!(metadata.frames(AnimationKind::Attack, Direction::East), &[2, 3]);
+        assert_eq!(metadata.frames(AnimationKind::Walk, Direction::South), &[0]);
+        assert_eq!(metadata.frames(AnimationKind::Hurt, Direction::North), &[0]);
+    }
+
+    #[test]
+    fn keypoints_follow_the_animation_frame() {
+        let hand = |x: f32, y: f32| vec![Keypoint { label: "RIGHT ARM".to_string(), x, y }];
+        let metadata = CharacterMetadata {
+            size: UVec2::splat(48),
+            keypoints: HashMap::from([(Direction::South, hand(20.0, 30.0)), (Direction::East, hand(24.0, 31.0))]),
+            atlas: Handle::default(),
+            layout: Handle::default(),
+            stills: HashMap::from([(Direction::South, 0), (Direction::East, 1)]),
+            clips: HashMap::from([
+                ((AnimationKind::Walk, Direction::East), vec![2, 3]),
+                ((AnimationKind::Attack, Direction::East), vec![4, 5]),
+            ]),
+            clip_keypoints: HashMap::from([((AnimationKind::Walk, Direction::East), vec![hand(30.0, 24.0), hand(36.0, 12.0)])]),
+        };
+
+        // 像素座標轉成以中心為原點、y 向上
+        assert_eq!(metadata.keypoint(AnimationKind::Walk, Direction::East, 1, "RIGHT ARM"), Some(Vec2::new(12.0, 12.0)));
+        // 超出影格數時使用最後一格
+        assert_eq!(metadata.keypoint(AnimationKind::Walk, Direction::East, 9, "RIGHT ARM"), Some(Vec2::new(12.0, 12.0)));
+        // 動畫沒有關鍵點時用靜止圖的
+        assert_eq!(metadata.keypoint(AnimationKind::Attack, Direction::East, 0, "RIGHT ARM"), Some(Vec2::new(0.0, -7.0)));
+        assert_eq!(metadata.keypoint(AnimationKind::Idle, Direction::West, 0, "RIGHT ARM"), Some(Vec2::new(-4.0, -6.0)));
+        assert_eq!(metadata.keypoint(AnimationKind::Idle, Direction::East, 0, "LEFT ARM"), None);
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::constants::*;
+
+// 推出牆壁時額外留的距離，避免浮點誤差讓物體卡在牆邊
+const SKIN: f32 = 0.01;
+
+/// 瓷磚佔用表 - 記錄哪些格子會阻擋移動，由 RoomTile 建立
+#[derive(Resource)]
+pub struct TileCollisionMap {
+    pub tile_size: f32,
+    pub solid: HashSet<IVec2>,
+}
+
+impl Default for TileCollisionMap {
+    fn default() -> Self {
+        Self {
+            tile_size: ROOM_TILE_SIZE * PLAYER_SCALE,
+            solid: HashSet::new(),
+        }
+    }
+}
+
+impl TileCollisionMap {
+    /// 世界座標所在的格子（瓷磚中心位於 格子 * tile_size）
+    pub fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.tile_size + Vec2::splat(0.5)).floor().as_ivec2()
+    }
+
+    pub fn is_solid(&self, cell: IVec2) -> bool {
+        self.solid.contains(&cell)
+    }
+
+    /// 軸分離移動：先移動 X 再移動 Y，撞牆的軸會被推回牆邊，另一軸繼續滑動
+    ///
+    /// 回傳新位置以及哪個軸被牆擋住
+    pub fn move_and_slide(&self, position: Vec2, half_size: Vec2, delta: Vec2) -> (Vec2, BVec2) {
+        // 每一步不超過半格，避免高速物體穿牆
+        let max_step = self.tile_size * 0.5;
+        let steps = (delta.abs().max_element() / max_step).ceil().max(1.0) as u32;
+        let step = delta / steps as f32;
+
+        let mut position = position;
+        let mut blocked = BVec2::FALSE;
+        for _ in 0..steps {
+            for axis in 0..2 {
+                if step[axis] == 0.0 || blocked.test(axis) {
+                    continue;
+                }
+                let from = position[axis];
+                position[axis] += step[axis];
+                if let Some(resolved) = self.resolve_axis(position, half_size, axis, from) {
+                    position[axis] = resolved;
+                    blocked.set(axis, true);
+                }
+            }
+        }
+
+        (position, blocked)
+    }
+
+    /// 沿著一個軸（0 為 X、1 為 Y）從 from 移動到 position 後推出牆壁，回傳推回後的座標
+    ///
+    /// 只看移動方向前方的格子：移動前就重疊的牆（例如關在身上的門）不會把物體往回推
+    fn resolve_axis(&self, position: Vec2, half_size: Vec2, axis: usize, from: f32) -> Option<f32> {
+        let direction = (position[axis] - from).signum();
+        let (min, max) = self.cell_range(position, half_size);
+        let reach = self.tile_size * 0.5 + half_size[axis] + SKIN;
+
+        let mut resolved: Option<f32> = None;
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                if !self.is_solid(cell) {
+                    continue;
+                }
+                let candidate = cell[axis] as f32 * self.tile_size - direction * reach;
+                // 推回位置在起點後方的是移動前就重疊的牆
+                if (candidate - from) * direction < -SKIN {
+                    continue;
+                }
+                // 取最靠近起點的推回位置
+                resolved = Some(match resolved {
+                    Some(current) if (current - candidate) * direction <= 0.0 => current,
+                    _ => candidate,
+                });
+            }
+        }
+        resolved
+    }
+
+    /// AABB 覆蓋的格子範圍（只有真正重疊才算，剛好貼邊不算）
+    fn cell_range(&self, center: Vec2, half_size: Vec2) -> (IVec2, IVec2) {
+        let min = ((center - half_size) / self.tile_size + Vec2::splat(0.5)).floor().as_ivec2();
+        let max = ((center + half_size) / self.tile_size + Vec2::splat(0.5)).ceil().as_ivec2() - IVec2::ONE;
+        (min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE: f32 = 16.0;
+    const HALF: Vec2 = Vec2::splat(5.0);
+
+    fn map(solid: &[(i32, i32)]) -> TileCollisionMap {
+        TileCollisionMap {
+            tile_size: TILE,
+            solid: solid.iter().map(|&(x, y)| IVec2::new(x, y)).collect(),
+        }
+    }
+
+    // 推回後貼著牆面，只差 SKIN
+    fn flush(tile: i32, direction: f32) -> f32 {
+        tile as f32 * TILE - direction * (TILE * 0.5 + HALF.x + SKIN)
+    }
+
+    #[test]
+    fn slides_along_a_straight_wall() {
+        let wall: Vec<(i32, i32)> = (-5..=5).map(|y| (1, y)).collect();
+        let (position, blocked) = map(&wall).move_and_slide(Vec2::ZERO, HALF, Vec2::new(10.0, 20.0));
+
+        assert!((position.x - flush(1, 1.0)).abs() < 1e-4);
+        assert_eq!(position.y, 20.0);
+        assert_eq!(blocked, BVec2::new(true, false));
+    }
+
+    #[test]
+    fn stops_in_an_inside_corner() {
+        let (position, blocked) = map(&[(1, -1), (1, 0), (1, 1), (0, 1), (-1, 1)])
+            .move_and_slide(Vec2::ZERO, HALF, Vec2::new(12.0, 12.0));
+
+        assert!((position.x - flush(1, 1.0)).abs() < 1e-4);
+        assert!((position.y - flush(1, 1.0)).abs() < 1e-4);
+        assert_eq!(blocked, BVec2::TRUE);
+    }
+
+    #[test]
+    fn does_not_pass_between_tiles_touching_at_a_corner() {
+        let (position, blocked) = map(&[(1, 0), (0, 1)]).move_and_slide(Vec2::ZERO, HALF, Vec2::splat(TILE));
+
+        assert_eq!(map(&[]).cell_of(position), IVec2::ZERO);
+        assert_eq!(blocked, BVec2::TRUE);
+    }
+
+    #[test]
+    fn fast_moves_do_not_tunnel_through_walls() {
+        let (position, blocked) = map(&[(2, 0)]).move_and_slide(Vec2::ZERO, HALF, Vec2::new(TILE * 5.0, 0.0));
+
+        assert!((position.x - flush(2, 1.0)).abs() < 1e-4);
+        assert_eq!(blocked, BVec2::new(true, false));
+    }
+
+    #[test]
+    fn overlapping_walls_do_not_push_backwards() {
+        // 門關在身上：往外走可以離開，不會被推到門的另一側
+        let (position, blocked) = map(&[(0, 0)]).move_and_slide(Vec2::new(2.0, 0.0), HALF, Vec2::new(4.0, 0.0));
+
+        assert_eq!(position, Vec2::new(6.0, 0.0));
+        assert_eq!(blocked, BVec2::FALSE);
+    }
+}
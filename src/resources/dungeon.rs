@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::world::{CompoundRoom, DoorSocket};
+use crate::constants::*;
+
+/// 樓層中的一個房間節點
+#[derive(Debug, Clone)]
+pub struct RoomNode {
+    pub slot: IVec2,          // 在樓層佈局格中的位置
+    pub layout: CompoundRoom, // 房間形狀（矩形已平移到世界格子座標）
+    pub template: Option<String>,       // 手工樣板名稱，程序生成的房間為 None
+    pub door_sockets: Vec<DoorSocket>,  // 手工樣板指定的開門位置（世界格子座標）
+    pub spawn_points: Vec<IVec2>,       // 手工樣板指定的敵人生成點（世界格子座標）
+}
+
+/// 兩個房間之間的連線（門與走廊）
+#[derive(Debug, Clone)]
+pub struct RoomEdge {
+    pub a: usize,
+    pub b: usize,
+    pub door_a: IVec2,          // 房間 a 牆上的門格子
+    pub door_b: IVec2,          // 房間 b 牆上的門格子
+    pub corridor: Vec<IVec2>,   // 走廊地板格子（不含門）
+    pub locked: bool,           // a 側的門需要鑰匙（只會是捷徑，不影響樓層連通）
+}
+
+/// 樓層房間圖 - 由樓層生成器建立，供其他系統查詢房間與連通關係
+#[derive(Resource, Debug, Clone)]
+pub struct RoomGraph {
+    pub rooms: Vec<RoomNode>,
+    pub edges: Vec<RoomEdge>,
+    pub start_room: usize,
+}
+
+impl RoomGraph {
+    /// 與指定房間相連的房間
+    pub fn neighbors(&self, room: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges.iter().filter_map(move |edge| {
+            if edge.a == room {
+                Some(edge.b)
+            } else if edge.b == room {
+                Some(edge.a)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// 指定房間牆上的所有門格子
+    pub fn doors(&self, room: usize) -> impl Iterator<Item = IVec2> + '_ {
+        self.edges.iter().filter_map(move |edge| {
+            if edge.a == room {
+                Some(edge.door_a)
+            } else if edge.b == room {
+                Some(edge.door_b)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// 世界座標位於哪個房間的地板上（走廊與牆壁上回傳 None）
+    pub fn room_at(&self, position: Vec2) -> Option<usize> {
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let cell = (position / tile_size + Vec2::splat(0.5)).floor().as_ivec2();
+
+        self.rooms.iter().position(|node| node.layout.is_floor(cell))
+    }
+
+    /// 房間佔用範圍（含牆壁）的世界座標邊界，以瓷磚邊緣為界
+    pub fn room_bounds(&self, room: usize) -> Rect {
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let (min, max) = self.rooms[room].layout.footprint_bounds();
+        Rect::from_corners(
+            (min.as_vec2() - Vec2::splat(0.5)) * tile_size,
+            (max.as_vec2() + Vec2::splat(0.5)) * tile_size,
+        )
+    }
+
+    /// 世界座標位於哪條連線的走廊或門上，回傳 edges 的索引
+    pub fn corridor_at(&self, position: Vec2) -> Option<usize> {
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let cell = (position / tile_size + Vec2::splat(0.5)).floor().as_ivec2();
+
+        self.edges.iter().position(|edge| {
+            edge.door_a == cell || edge.door_b == cell || edge.corridor.contains(&cell)
+        })
+    }
+
+    /// 連線兩端的房間加上走廊的世界座標邊界
+    pub fn corridor_bounds(&self, edge: usize) -> Rect {
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let edge = &self.edges[edge];
+        edge.corridor.iter()
+            .map(|cell| Rect::from_center_size(cell.as_vec2() * tile_size, Vec2::splat(tile_size)))
+            .fold(self.room_bounds(edge.a).union(self.room_bounds(edge.b)), |bounds, tile| bounds.union(tile))
+    }
+
+    /// 房間中心（第一個矩形的地板中心）的世界座標
+    pub fn room_center(&self, room: usize) -> Vec2 {
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let (min, max) = self.rooms[room].layout.rectangles[0].floor_cells();
+        ((min + max) / 2).as_vec2() * tile_size
+    }
+}
+
+/// 玩家目前所在的房間（在走廊時保留上一個房間）
+#[derive(Resource, Default)]
+pub struct CurrentRoom(pub Option<usize>);
+
+/// 敵人已全部被擊倒的房間
+#[derive(Resource, Default, Debug)]
+pub struct ClearedRooms(pub HashSet<usize>);
+
+/// 已經撿起的樓層物品（以放置的格子記錄，讀檔時不再生成）
+#[derive(Resource, Default, Debug)]
+pub struct CollectedItems(pub HashSet<IVec2>);
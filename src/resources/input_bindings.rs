@@ -0,0 +1,350 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use crate::constants::*;
+
+/// 遊戲動作 - 遊戲系統只讀動作，不直接讀按鍵
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Attack,
+    Interact,
+    Dash,
+    Pause,
+    SwitchWeapon,
+    UseItem,
+    TimingAttack,
+    SaveAndQuit,   // 暫停畫面：存檔並回到主選單
+    ContinueRun,   // 主選單：讀取存檔繼續上一局
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 13] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Attack,
+        InputAction::Interact,
+        InputAction::Dash,
+        InputAction::Pause,
+        InputAction::SwitchWeapon,
+        InputAction::UseItem,
+        InputAction::TimingAttack,
+        InputAction::SaveAndQuit,
+        InputAction::ContinueRun,
+    ];
+
+    /// 顯示在按鍵設定畫面上的名稱
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move Up",
+            InputAction::MoveDown => "Move Down",
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::Attack => "Attack",
+            InputAction::Interact => "Interact",
+            InputAction::Dash => "Dash",
+            InputAction::Pause => "Pause",
+            InputAction::SwitchWeapon => "Switch Weapon",
+            InputAction::UseItem => "Use Potion",
+            InputAction::TimingAttack => "Timing Attack",
+            InputAction::SaveAndQuit => "Save & Quit",
+            InputAction::ContinueRun => "Continue Run",
+        }
+    }
+}
+
+/// 單一按鍵綁定（鍵盤按鍵或手把按鈕）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    fn is_same_device(&self, other: &InputBinding) -> bool {
+        matches!(
+            (self, other),
+            (InputBinding::Key(_), InputBinding::Key(_)) | (InputBinding::Gamepad(_), InputBinding::Gamepad(_))
+        )
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputBinding::Key(key) => write!(f, "{key:?}"),
+            InputBinding::Gamepad(button) => write!(f, "Pad {button:?}"),
+        }
+    }
+}
+
+/// 動作與按鍵的對應 - 從設定檔載入，重新綁定後寫回設定檔
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub bindings: BTreeMap<InputAction, Vec<InputBinding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use InputBinding::{Gamepad, Key};
+
+        let bindings = BTreeMap::from([
+            (InputAction::MoveUp, vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)]),
+            (InputAction::MoveDown, vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)]),
+            (InputAction::MoveLeft, vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft), Gamepad(GamepadButton::DPadLeft)]),
+            (InputAction::MoveRight, vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight), Gamepad(GamepadButton::DPadRight)]),
+            (InputAction::Attack, vec![Key(KeyCode::Space), Gamepad(GamepadButton::West)]),
+            (InputAction::Interact, vec![Key(KeyCode::KeyE), Gamepad(GamepadButton::South)]),
+            (InputAction::Dash, vec![Key(KeyCode::ShiftLeft), Gamepad(GamepadButton::East)]),
+            (InputAction::Pause, vec![Key(KeyCode::Escape), Gamepad(GamepadButton::Start)]),
+            (InputAction::SwitchWeapon, vec![Key(KeyCode::Tab), Gamepad(GamepadButton::North)]),
+            (InputAction::UseItem, vec![Key(KeyCode::KeyF), Gamepad(GamepadButton::RightTrigger)]),
+            (InputAction::TimingAttack, vec![Key(KeyCode::KeyT), Gamepad(GamepadButton::LeftTrigger)]),
+            (InputAction::SaveAndQuit, vec![Key(KeyCode::KeyQ), Gamepad(GamepadButton::Select)]),
+            (InputAction::ContinueRun, vec![Key(KeyCode::KeyC)]),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+/// 按鍵設定檔讀寫錯誤
+#[derive(Debug)]
+pub enum InputConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+}
+
+impl fmt::Display for InputConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputConfigError::Io(error) => write!(f, "無法讀寫按鍵設定檔: {error}"),
+            InputConfigError::Parse(error) => write!(f, "按鍵設定檔格式錯誤: {error}"),
+            InputConfigError::Serialize(error) => write!(f, "無法序列化按鍵設定: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for InputConfigError {}
+
+impl From<std::io::Error> for InputConfigError {
+    fn from(error: std::io::Error) -> Self {
+        InputConfigError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for InputConfigError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        InputConfigError::Parse(error)
+    }
+}
+
+impl From<ron::Error> for InputConfigError {
+    fn from(error: ron::Error) -> Self {
+        InputConfigError::Serialize(error)
+    }
+}
+
+impl InputBindings {
+    /// 解析設定檔；設定檔沒有列出的動作使用預設按鍵
+    pub fn from_ron(text: &str) -> Result<Self, InputConfigError> {
+        let mut bindings: InputBindings = ron::from_str(text)?;
+        for (action, defaults) in InputBindings::default().bindings {
+            bindings.bindings.entry(action).or_insert(defaults);
+        }
+        Ok(bindings)
+    }
+
+    pub fn to_ron(&self) -> Result<String, InputConfigError> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+
+    /// 讀取設定檔；檔案不存在或無法解析時使用預設按鍵
+    pub fn load_or_default(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_ron(&text).unwrap_or_else(|error| {
+                warn!("{}：{}，改用預設按鍵", path.display(), error);
+                Self::default()
+            }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(error) => {
+                warn!("無法讀取 {}：{}，改用預設按鍵", path.display(), error);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), InputConfigError> {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// 把按鍵綁定到動作：取代該動作同一種裝置的舊按鍵
+    ///
+    /// 按鍵原本屬於其他動作時兩者交換，那個動作改用被取代的舊按鍵，不會因此沒有按鍵；
+    /// 回傳交換的動作
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) -> Option<InputAction> {
+        let bindings = self.bindings.entry(action).or_default();
+        let mut replaced: Option<Vec<InputBinding>> = Some(bindings.iter().copied()
+            .filter(|existing| existing.is_same_device(&binding) && *existing != binding)
+            .collect());
+        bindings.retain(|existing| !existing.is_same_device(&binding));
+        bindings.push(binding);
+
+        let mut swapped = None;
+        for (other, bindings) in &mut self.bindings {
+            if *other == action {
+                continue;
+            }
+            let Some(index) = bindings.iter().position(|existing| *existing == binding) else {
+                continue;
+            };
+            // 只有第一個衝突的動作拿到舊按鍵
+            bindings.splice(index..=index, replaced.take().unwrap_or_default());
+            swapped.get_or_insert(*other);
+        }
+        swapped
+    }
+
+    /// 動作目前的按鍵，例如 "Escape / Pad Start"
+    pub fn describe(&self, action: InputAction) -> String {
+        let labels: Vec<String> = self.get(action).iter().map(ToString::to_string).collect();
+        if labels.is_empty() {
+            "(unbound)".to_string()
+        } else {
+            labels.join(" / ")
+        }
+    }
+}
+
+/// 這一幀的動作狀態（由鍵盤與所有手把合併而成）
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+    movement: Vec2,
+}
+
+impl ActionState {
+    pub fn update(&mut self, bindings: &InputBindings, keyboard: &ButtonInput<KeyCode>, gamepads: &[&Gamepad]) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+
+        for (action, action_bindings) in &bindings.bindings {
+            for binding in action_bindings {
+                let (pressed, just_pressed) = match binding {
+                    InputBinding::Key(key) => (keyboard.pressed(*key), keyboard.just_pressed(*key)),
+                    InputBinding::Gamepad(button) => (
+                        gamepads.iter().any(|gamepad| gamepad.pressed(*button)),
+                        gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)),
+                    ),
+                };
+                if pressed {
+                    self.pressed.insert(*action);
+                }
+                if just_pressed {
+                    self.just_pressed.insert(*action);
+                }
+            }
+        }
+
+        let axis = |positive: InputAction, negative: InputAction| {
+            self.pressed(positive) as i32 as f32 - self.pressed(negative) as i32 as f32
+        };
+        let digital = Vec2::new(
+            axis(InputAction::MoveRight, InputAction::MoveLeft),
+            axis(InputAction::MoveUp, InputAction::MoveDown),
+        );
+        let stick = gamepads.iter()
+            .map(|gamepad| gamepad.left_stick())
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+            .unwrap_or(Vec2::ZERO);
+        self.movement = combine_movement(digital, stick, INPUT_DEADZONE);
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// 移動方向，長度不超過 1（類比搖桿推一半就是一半的速度）
+    pub fn movement(&self) -> Vec2 {
+        self.movement
+    }
+}
+
+/// 合併按鍵方向與類比搖桿：搖桿超過 deadzone 才採用，否則使用正規化後的按鍵方向
+pub fn combine_movement(digital: Vec2, stick: Vec2, deadzone: f32) -> Vec2 {
+    if stick.length() > deadzone {
+        stick.clamp_length_max(1.0)
+    } else {
+        digital.normalize_or_zero()
+    }
+}
+
+/// 等待玩家按下新按鍵的動作（按鍵設定畫面）
+#[derive(Resource, Default)]
+pub struct PendingRebind(pub Option<InputAction>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_round_trip_and_fill_missing_actions() {
+        let bindings = InputBindings::default();
+        assert_eq!(InputBindings::from_ron(&bindings.to_ron().unwrap()).unwrap(), bindings);
+
+        let partial = InputBindings::from_ron("(bindings: { Attack: [Key(KeyJ)] })").unwrap();
+        assert_eq!(partial.get(InputAction::Attack), &[InputBinding::Key(KeyCode::KeyJ)]);
+        assert_eq!(partial.get(InputAction::Pause), InputBindings::default().get(InputAction::Pause));
+        assert!(InputBindings::from_ron("(bindings: { Attack: [Key(NotAKey)] })").is_err());
+    }
+
+    #[test]
+    fn rebind_replaces_same_device_and_swaps_conflicts() {
+        let mut bindings = InputBindings::default();
+        let swapped = bindings.rebind(InputAction::Attack, InputBinding::Key(KeyCode::KeyE));
+
+        assert_eq!(swapped, Some(InputAction::Interact));
+        assert_eq!(
+            bindings.get(InputAction::Attack),
+            &[InputBinding::Gamepad(GamepadButton::West), InputBinding::Key(KeyCode::KeyE)],
+        );
+        // 互動改用攻擊原本的按鍵，仍然有鍵盤按鍵
+        assert_eq!(
+            bindings.get(InputAction::Interact),
+            &[InputBinding::Key(KeyCode::Space), InputBinding::Gamepad(GamepadButton::South)],
+        );
+
+        // 綁定沒有衝突的按鍵不會交換
+        assert_eq!(bindings.rebind(InputAction::Dash, InputBinding::Key(KeyCode::KeyX)), None);
+        assert_eq!(bindings.get(InputAction::Dash), &[InputBinding::Gamepad(GamepadButton::East), InputBinding::Key(KeyCode::KeyX)]);
+    }
+
+    #[test]
+    fn stick_overrides_keys_only_outside_deadzone() {
+        let deadzone = 0.1;
+        assert_eq!(combine_movement(Vec2::new(1.0, 1.0), Vec2::new(0.05, 0.0), deadzone), Vec2::new(1.0, 1.0).normalize());
+        assert_eq!(combine_movement(Vec2::ZERO, Vec2::new(0.5, 0.0), deadzone), Vec2::new(0.5, 0.0));
+        assert!((combine_movement(Vec2::ZERO, Vec2::new(0.9, 0.9), deadzone).length() - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// 玩家目前可以互動的物件（範圍內最近的 `Interactable`）
+#[derive(Resource, Default, Debug)]
+pub struct InteractionTarget(pub Option<Entity>);
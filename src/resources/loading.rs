@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy::asset::{RecursiveDependencyLoadState, UntypedAssetId};
+
+/// 開始遊戲前必須載入完成的資源清單
+#[derive(Resource, Default)]
+pub struct AssetManifest {
+    handles: Vec<UntypedHandle>,
+}
+
+/// 資源清單目前的載入進度
+#[derive(Debug, Default, PartialEq)]
+pub struct LoadProgress {
+    pub loaded: usize,
+    pub total: usize,
+    pub failed: Vec<(String, String)>,  // (檔案路徑, 錯誤訊息)
+}
+
+impl LoadProgress {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.loaded == self.total
+    }
+}
+
+impl AssetManifest {
+    /// 載入資源並加入清單
+    pub fn load<A: Asset>(&mut self, asset_server: &AssetServer, path: &str) -> Handle<A> {
+        let handle = asset_server.load(path.to_string());
+        self.handles.push(handle.clone().untyped());
+        handle
+    }
+
+    /// 計算進度（含相依資源，例如角色描述檔引用的圖片）
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadProgress {
+        let mut progress = LoadProgress {
+            total: self.handles.len(),
+            ..default()
+        };
+
+        for handle in &self.handles {
+            match asset_server.recursive_dependency_load_state(handle.id()) {
+                RecursiveDependencyLoadState::Loaded => progress.loaded += 1,
+                RecursiveDependencyLoadState::Failed(error) => {
+                    progress.failed.push((path_of(asset_server, handle.id()), error.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        progress
+    }
+}
+
+fn path_of(asset_server: &AssetServer, id: UntypedAssetId) -> String {
+    asset_server.get_path(id)
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{id:?}"))
+}
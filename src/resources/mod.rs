@@ -1,7 +1,29 @@
 // Resources module declarations
 pub mod sprites;
 pub mod room_assets;
+pub mod collision_map;
+pub mod dungeon;
+pub mod seed;
+pub mod room_template;
+pub mod loading;
+pub mod character_metadata;
+pub mod save;
+pub mod input_bindings;
+pub mod interaction;
+pub mod player_archetype;
+pub mod weapons;
 
 // Re-export all resources for easy importing
 pub use sprites::*;
-pub use room_assets::*;
\ No newline at end of file
+pub use room_assets::*;
+pub use collision_map::*;
+pub use dungeon::*;
+pub use seed::*;
+pub use room_template::*;
+pub use loading::*;
+pub use character_metadata::*;
+pub use save::*;
+pub use input_bindings::*;
+pub use interaction::*;
+pub use player_archetype::*;
+pub use weapons::*;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use crate::components::attack::WeaponType;
+use crate::constants::*;
+
+/// 玩家角色設定 - 生成玩家時使用的能力值、外觀與起始武器（武器的數值在 WeaponCatalog）
+///
+/// 所有玩家功能都只讀這份設定，換角色或調整數值不需要改生成系統
+#[derive(Resource, Debug, Clone)]
+pub struct PlayerArchetype {
+    pub sprite_size: f32,          // 圖片在本地座標的大小
+    pub scale: f32,                // 實體縮放
+    pub hurtbox_radius: f32,
+    pub collider_half_size: f32,   // 碰撞箱半寬（像素）
+    pub starting_weapon: WeaponType,
+    pub levels: Vec<PlayerLevel>,  // 等級表，第一筆是 1 級
+}
+
+/// 等級表的一筆 - 到達這個等級需要的總經驗值與這個等級的能力值
+#[derive(Debug, Clone)]
+pub struct PlayerLevel {
+    pub xp_required: u32,
+    pub max_health: i32,
+    pub attack_damage: i32,
+    pub speed: f32,
+    pub character: &'static str,   // assets/characters 底下的角色資料夾名稱
+}
+
+impl PlayerArchetype {
+    /// 指定等級的能力值；超過等級表時使用最高等級
+    pub fn level(&self, level: u32) -> &PlayerLevel {
+        let index = (level.max(1) as usize - 1).min(self.levels.len() - 1);
+        &self.levels[index]
+    }
+
+    /// 累積這麼多經驗值時的等級
+    pub fn level_for_xp(&self, xp: u32) -> u32 {
+        self.levels.iter().take_while(|level| xp >= level.xp_required).count().max(1) as u32
+    }
+}
+
+impl Default for PlayerArchetype {
+    /// 騎士英雄 - 1 級是有完整方向圖片的 knight_hero，2 級起換成等級騎士
+    ///
+    /// knight_lv2、knight_lv3 只有一張圖片，各方向共用
+    fn default() -> Self {
+        let level = |xp_required, max_health, attack_damage, speed, character| PlayerLevel {
+            xp_required,
+            max_health,
+            attack_damage,
+            speed,
+            character,
+        };
+        Self {
+            sprite_size: PLAYER_SPRITE_SIZE,
+            scale: PLAYER_ENTITY_SCALE,
+            hurtbox_radius: PLAYER_HURTBOX_RADIUS,
+            collider_half_size: PLAYER_COLLIDER_HALF_SIZE,
+            starting_weapon: WeaponType::Sword,
+            levels: vec![
+                level(0, PLAYER_MAX_HEALTH, PLAYER_ATTACK_DAMAGE, PLAYER_SPEED, "knight_hero"),
+                level(50, 120, 30, 210.0, "knight_lv2"),
+                level(120, 140, 35, 220.0, "knight_lv3"),
+                level(220, 165, 41, 230.0, "knight_lv3"),
+                level(350, 190, 48, 240.0, "knight_lv3"),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::CharacterMetadataHandles;
+
+    #[test]
+    fn levels_follow_the_xp_table() {
+        let archetype = PlayerArchetype::default();
+        assert_eq!(archetype.level_for_xp(0), 1);
+        assert_eq!(archetype.level_for_xp(49), 1);
+        assert_eq!(archetype.level_for_xp(50), 2);
+        assert_eq!(archetype.level_for_xp(10_000), archetype.levels.len() as u32);
+        assert_eq!(archetype.level(99).xp_required, 350);
+        assert_eq!(archetype.level(0).max_health, PLAYER_MAX_HEALTH);
+
+        // 每一級的角色圖片都有載入，升到 2、3 級時換圖
+        for level in &archetype.levels {
+            assert!(CharacterMetadataHandles::CHARACTERS.contains(&level.character), "{} 沒有載入", level.character);
+        }
+        assert_ne!(archetype.level(1).character, archetype.level(2).character);
+        assert_ne!(archetype.level(2).character, archetype.level(3).character);
+
+        // 每一級都比上一級需要更多經驗、能力更強
+        for pair in archetype.levels.windows(2) {
+            assert!(pair[1].xp_required > pair[0].xp_required);
+            assert!(pair[1].max_health >= pair[0].max_health);
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use crate::components::world::RoomTileType;
+use crate::resources::AssetManifest;
 
 #[derive(Resource)]
 pub struct RoomAssets {
@@ -28,31 +30,52 @@ pub struct RoomAssets {
 }
 
 impl RoomAssets {
-    pub fn load_all(asset_server: &AssetServer) -> Self {
+    pub fn load_all(asset_server: &AssetServer, manifest: &mut AssetManifest) -> Self {
+        let mut load = |path: &str| manifest.load(asset_server, path);
         Self {
             // Load floor assets
-            floor_indoor: asset_server.load("floors/floor_indoor.png"),
+            floor_indoor: load("floors/floor_indoor.png"),
             
             // Load north wall assets
-            wall_n_inner_corner_w: asset_server.load("walls/wall_N_inner_corner_W.png"),
-            wall_n_inner_mid: asset_server.load("walls/wall_N_inner_mid.png"),
-            wall_n_inner_corner_e: asset_server.load("walls/wall_N_inner_corner_E.png"),
+            wall_n_inner_corner_w: load("walls/wall_N_inner_corner_W.png"),
+            wall_n_inner_mid: load("walls/wall_N_inner_mid.png"),
+            wall_n_inner_corner_e: load("walls/wall_N_inner_corner_E.png"),
             
             // Load south wall assets
-            wall_s_inner_cap_l: asset_server.load("walls/wall_S_inner_cap_L.png"),
-            wall_s_inner_mid: asset_server.load("walls/wall_S_inner_mid.png"),
-            wall_s_inner_cap_r: asset_server.load("walls/wall_S_inner_cap_R.png"),
-            wall_s_outer_cap_l: asset_server.load("walls/wall_S_outer_cap_L.png"),
-            wall_s_outer_mid: asset_server.load("walls/wall_S_outer_mid.png"),
-            wall_s_outer_cap_r: asset_server.load("walls/wall_S_outer_cap_R.png"),
+            wall_s_inner_cap_l: load("walls/wall_S_inner_cap_L.png"),
+            wall_s_inner_mid: load("walls/wall_S_inner_mid.png"),
+            wall_s_inner_cap_r: load("walls/wall_S_inner_cap_R.png"),
+            wall_s_outer_cap_l: load("walls/wall_S_outer_cap_L.png"),
+            wall_s_outer_mid: load("walls/wall_S_outer_mid.png"),
+            wall_s_outer_cap_r: load("walls/wall_S_outer_cap_R.png"),
             
             // Load side wall assets
-            wall_w_side: asset_server.load("walls/wall_W_side.png"),
-            wall_e_side: asset_server.load("walls/wall_E_side.png"),
+            wall_w_side: load("walls/wall_W_side.png"),
+            wall_e_side: load("walls/wall_E_side.png"),
             
             // Load door assets
-            door_closed: asset_server.load("doors/door_closed.png"),
-            door_open: asset_server.load("doors/door_open.png"),
+            door_closed: load("doors/door_closed.png"),
+            door_open: load("doors/door_open.png"),
+        }
+    }
+
+    /// 瓷磚類型對應的圖片
+    pub fn image_for(&self, tile_type: RoomTileType) -> Handle<Image> {
+        match tile_type {
+            RoomTileType::Floor => self.floor_indoor.clone(),
+            RoomTileType::WallNInnerCornerW => self.wall_n_inner_corner_w.clone(),
+            RoomTileType::WallNInnerMid => self.wall_n_inner_mid.clone(),
+            RoomTileType::WallNInnerCornerE => self.wall_n_inner_corner_e.clone(),
+            RoomTileType::WallSInnerCapL => self.wall_s_inner_cap_l.clone(),
+            RoomTileType::WallSInnerMid => self.wall_s_inner_mid.clone(),
+            RoomTileType::WallSInnerCapR => self.wall_s_inner_cap_r.clone(),
+            RoomTileType::WallSOuterCapL => self.wall_s_outer_cap_l.clone(),
+            RoomTileType::WallSOuterMid => self.wall_s_outer_mid.clone(),
+            RoomTileType::WallSOuterCapR => self.wall_s_outer_cap_r.clone(),
+            RoomTileType::WallWSide => self.wall_w_side.clone(),
+            RoomTileType::WallESide => self.wall_e_side.clone(),
+            RoomTileType::DoorClosed => self.door_closed.clone(),
+            RoomTileType::DoorOpen => self.door_open.clone(),
         }
     }
 }
\ No newline at end of file
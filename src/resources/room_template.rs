@@ -0,0 +1,391 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::asset::io::Reader;
+use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use crate::components::world::{CompoundRoom, CompoundRoomType, DoorSocket, RoomRect, WallSide};
+use crate::constants::*;
+
+/// 手工房間樣板 - 從 assets/rooms/*.room.ron 載入，座標已置中於原點
+///
+/// 樓層生成器會把它平移到佈局格上，產生 `CompoundRoomType::Custom` 房間
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct RoomTemplate {
+    pub name: String,
+    pub weight: u32,                  // 被選中的相對機率
+    pub tags: Vec<String>,
+    pub rectangles: Vec<RoomRect>,
+    pub door_sockets: Vec<DoorSocket>,
+    pub spawn_points: Vec<IVec2>,     // 敵人生成點（地板格子）
+}
+
+/// 樣板資料夾的載入 handle
+#[derive(Resource)]
+pub struct RoomTemplateFolder(pub Handle<LoadedFolder>);
+
+// ---- 檔案格式 ----
+
+#[derive(Deserialize)]
+struct RoomTemplateDef {
+    name: String,
+    #[serde(default = "default_weight")]
+    weight: u32,
+    #[serde(default)]
+    tags: Vec<String>,
+    shape: RoomShapeDef,
+    #[serde(default)]
+    door_sockets: Vec<(i32, i32, WallSide)>,
+    #[serde(default)]
+    spawn_points: Vec<(i32, i32)>,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// 房間形狀：ASCII 地圖或地板矩形列表
+#[derive(Deserialize)]
+enum RoomShapeDef {
+    /// 第一行是最北邊，牆壁會自動圍在地板四周
+    /// `.` 地板、`e` 敵人生成點、`^` `v` `<` `>` 北/南/西/東牆的門插槽、空白或 `#` 為實心
+    Grid(Vec<String>),
+    /// 地板矩形 (x, y, 寬, 高)，y 軸向上
+    Rects(Vec<(i32, i32, usize, usize)>),
+}
+
+/// 樣板載入錯誤
+#[derive(Debug)]
+pub enum RoomTemplateError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for RoomTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoomTemplateError::Io(error) => write!(f, "無法讀取房間樣板: {error}"),
+            RoomTemplateError::Parse(error) => write!(f, "房間樣板格式錯誤: {error}"),
+            RoomTemplateError::Invalid(reason) => write!(f, "無效的房間樣板: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RoomTemplateError {}
+
+impl From<std::io::Error> for RoomTemplateError {
+    fn from(error: std::io::Error) -> Self {
+        RoomTemplateError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for RoomTemplateError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        RoomTemplateError::Parse(error)
+    }
+}
+
+#[derive(Default)]
+pub struct RoomTemplateLoader;
+
+impl AssetLoader for RoomTemplateLoader {
+    type Asset = RoomTemplate;
+    type Settings = ();
+    type Error = RoomTemplateError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<RoomTemplate, RoomTemplateError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        RoomTemplate::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["room.ron"]
+    }
+}
+
+impl RoomTemplate {
+    /// 解析並驗證 RON 格式的房間樣板
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, RoomTemplateError> {
+        let def: RoomTemplateDef = ron::de::from_bytes(bytes)?;
+        Self::from_def(def)
+    }
+
+    fn from_def(def: RoomTemplateDef) -> Result<Self, RoomTemplateError> {
+        let invalid = |reason: String| RoomTemplateError::Invalid(format!("{}: {}", def.name, reason));
+
+        let (floor_areas, door_sockets, spawn_points) = match &def.shape {
+            RoomShapeDef::Grid(rows) => parse_grid(rows).map_err(invalid)?,
+            RoomShapeDef::Rects(rects) => (rects.clone(), Vec::new(), Vec::new()),
+        };
+        let door_sockets: Vec<DoorSocket> = door_sockets.into_iter()
+            .chain(def.door_sockets.iter().map(|(x, y, side)| DoorSocket { cell: IVec2::new(*x, *y), side: *side }))
+            .collect();
+        let spawn_points: Vec<IVec2> = spawn_points.into_iter()
+            .chain(def.spawn_points.iter().map(|(x, y)| IVec2::new(*x, *y)))
+            .collect();
+
+        if floor_areas.is_empty() {
+            return Err(invalid("沒有任何地板".to_string()));
+        }
+        if def.weight == 0 {
+            return Err(invalid("weight 必須大於 0".to_string()));
+        }
+
+        // 地板矩形外擴一格就是含牆壁的房間矩形（與 RoomRect::floor_cells 相反）
+        let room = CompoundRoom {
+            rectangles: floor_areas.iter()
+                .map(|(x, y, width, height)| RoomRect { x: x - 1, y: y - 1, width: width + 2, height: height + 2 })
+                .collect(),
+            room_type: CompoundRoomType::Custom,
+        };
+
+        let (min, max) = room.footprint_bounds();
+        let size = max - min + IVec2::ONE;
+        if size.max_element() > ROOM_TEMPLATE_MAX_SIZE {
+            return Err(invalid(format!("尺寸 {}x{} 超過上限 {}", size.x, size.y, ROOM_TEMPLATE_MAX_SIZE)));
+        }
+
+        validate_connected(&room).map_err(invalid)?;
+        for socket in &door_sockets {
+            validate_socket(&room, socket).map_err(invalid)?;
+        }
+        if let Some(point) = spawn_points.iter().find(|point| !room.is_floor(**point)) {
+            return Err(invalid(format!("敵人生成點 {point} 不在地板上")));
+        }
+
+        // 置中到原點，與程序生成的房間一致
+        let offset = -(min + size / 2);
+        Ok(Self {
+            name: def.name,
+            weight: def.weight,
+            tags: def.tags,
+            rectangles: room.translated(offset).rectangles,
+            door_sockets: door_sockets.iter()
+                .map(|socket| DoorSocket { cell: socket.cell + offset, side: socket.side })
+                .collect(),
+            spawn_points: spawn_points.iter().map(|point| *point + offset).collect(),
+        })
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// 樣板的房間形狀（尚未平移）
+    pub fn layout(&self) -> CompoundRoom {
+        CompoundRoom {
+            rectangles: self.rectangles.clone(),
+            room_type: CompoundRoomType::Custom,
+        }
+    }
+}
+
+type GridContents = (Vec<(i32, i32, usize, usize)>, Vec<DoorSocket>, Vec<IVec2>);
+
+/// 把 ASCII 地圖拆成地板矩形：同一列連續的地板為一段，與上一列完全對齊的段落合併成同一個矩形
+fn parse_grid(rows: &[String]) -> Result<GridContents, String> {
+    let mut floor_areas: Vec<(i32, i32, usize, usize)> = Vec::new();
+    let mut door_sockets = Vec::new();
+    let mut spawn_points = Vec::new();
+    // 上一列的段落：(起點 x, 寬度, floor_areas 中的索引)
+    let mut open_runs: Vec<(i32, usize, usize)> = Vec::new();
+
+    // 由南往北處理，y 軸向上
+    for (y, row) in rows.iter().rev().enumerate() {
+        let y = y as i32;
+        let mut runs = Vec::new();
+        let mut run_start = None;
+
+        for (x, symbol) in row.chars().chain(std::iter::once(' ')).enumerate() {
+            let x = x as i32;
+            let cell = IVec2::new(x, y);
+            let is_floor = match symbol {
+                '.' => true,
+                'e' => {
+                    spawn_points.push(cell);
+                    true
+                }
+                '^' | 'v' | '<' | '>' => {
+                    let side = match symbol {
+                        '^' => WallSide::North,
+                        'v' => WallSide::South,
+                        '<' => WallSide::West,
+                        _ => WallSide::East,
+                    };
+                    door_sockets.push(DoorSocket { cell, side });
+                    true
+                }
+                ' ' | '#' => false,
+                other => return Err(format!("第 {} 行有未知的符號 {:?}", rows.len() as i32 - y, other)),
+            };
+
+            match (is_floor, run_start) {
+                (true, None) => run_start = Some(x),
+                (false, Some(start)) => {
+                    runs.push((start, (x - start) as usize));
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let mut next_open = Vec::new();
+        for (start, width) in runs {
+            let index = match open_runs.iter().find(|(s, w, _)| *s == start && *w == width) {
+                Some((_, _, index)) => {
+                    floor_areas[*index].3 += 1;
+                    *index
+                }
+                None => {
+                    floor_areas.push((start, y, width, 1));
+                    floor_areas.len() - 1
+                }
+            };
+            next_open.push((start, width, index));
+        }
+        open_runs = next_open;
+    }
+
+    Ok((floor_areas, door_sockets, spawn_points))
+}
+
+/// 所有地板必須四方向連通
+fn validate_connected(room: &CompoundRoom) -> Result<(), String> {
+    let floors: HashSet<IVec2> = room.rectangles.iter()
+        .flat_map(|rect| {
+            let (min, max) = rect.floor_cells();
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+        })
+        .collect();
+
+    let start = *floors.iter().next().ok_or("沒有任何地板")?;
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(cell) = queue.pop_front() {
+        for step in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            let next = cell + step;
+            if floors.contains(&next) && visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if visited.len() == floors.len() {
+        Ok(())
+    } else {
+        Err(format!("地板沒有完全連通（{} 格中只有 {} 格相連）", floors.len(), visited.len()))
+    }
+}
+
+/// 門插槽必須貼著外牆：插槽是地板，往牆外方向直到房間邊界都不能再有房間的格子，走廊才不會穿過房間
+fn validate_socket(room: &CompoundRoom, socket: &DoorSocket) -> Result<(), String> {
+    if !room.is_floor(socket.cell) {
+        return Err(format!("門插槽 {} 不在地板上", socket.cell));
+    }
+
+    let (min, max) = room.footprint_bounds();
+    let outward = match socket.side {
+        WallSide::North => IVec2::Y,
+        WallSide::South => IVec2::NEG_Y,
+        WallSide::East => IVec2::X,
+        WallSide::West => IVec2::NEG_X,
+    };
+    // 門本身（以及南牆門後方的內側牆）會被打通，從牆外那一格開始檢查
+    let mut cell = socket.door_cell() + outward;
+    while cell.cmpge(min).all() && cell.cmple(max).all() {
+        let in_footprint = room.rectangles.iter().any(|rect| {
+            cell.x >= rect.x && cell.x < rect.x + rect.width as i32
+                && cell.y >= rect.y - 1 && cell.y < rect.y + rect.height as i32
+        });
+        if in_footprint {
+            return Err(format!("門插槽 {} 不在外牆上", socket.cell));
+        }
+        cell += outward;
+    }
+
+    // 門所在的牆（南牆還包括門後方的內側牆）會被打通，不能是另一塊地板
+    let opened = if socket.side == WallSide::South {
+        vec![socket.cell - IVec2::Y, socket.door_cell()]
+    } else {
+        vec![socket.door_cell()]
+    };
+    if opened.iter().any(|cell| room.is_floor(*cell)) {
+        return Err(format!("門插槽 {} 外側還有地板", socket.cell));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_template_becomes_custom_room() {
+        let template = RoomTemplate::from_ron(br#"(
+            name: "pillars",
+            tags: ["combat"],
+            shape: Grid([
+                "..^..",
+                ".e.e.",
+                "<.#.>",
+                ".....",
+                "..v..",
+            ]),
+        )"#).unwrap();
+
+        assert_eq!(template.layout().room_type, CompoundRoomType::Custom);
+        assert!(template.has_tag("combat"));
+        assert_eq!(template.spawn_points.len(), 2);
+        assert_eq!(template.door_sockets.len(), 4);
+
+        let layout = template.layout();
+        let floor_count = template.rectangles.iter()
+            .map(|rect| (rect.width - 2) * (rect.height - 2))
+            .sum::<usize>();
+        assert_eq!(floor_count, 24, "地板矩形不應重疊");
+        for socket in &template.door_sockets {
+            assert!(layout.is_floor(socket.cell));
+            assert!(!layout.is_floor(socket.door_cell()));
+        }
+    }
+
+    #[test]
+    fn rect_template_accepts_explicit_sockets() {
+        let template = RoomTemplate::from_ron(br#"(
+            name: "twin",
+            weight: 3,
+            shape: Rects([(0, 0, 6, 4), (6, 1, 4, 2)]),
+            door_sockets: [(9, 1, East), (2, 0, South)],
+            spawn_points: [(1, 1)],
+        )"#).unwrap();
+
+        assert_eq!(template.weight, 3);
+        assert_eq!(template.rectangles.len(), 2);
+        assert_eq!(template.door_sockets.len(), 2);
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        let cases: [&[u8]; 4] = [
+            // 地板不連通
+            br#"(name: "split", shape: Grid(["..#..", "..#.."]))"#,
+            // 門插槽在內牆
+            br#"(name: "inner", shape: Grid([".....", ".>#..", "....."]))"#,
+            // 超過尺寸上限
+            br#"(name: "huge", shape: Rects([(0, 0, 40, 4)]))"#,
+            // 生成點不在地板上
+            br#"(name: "spawn", shape: Rects([(0, 0, 4, 4)]), spawn_points: [(9, 9)])"#,
+        ];
+        for bytes in cases {
+            assert!(matches!(RoomTemplate::from_ron(bytes), Err(RoomTemplateError::Invalid(_))));
+        }
+        assert!(matches!(RoomTemplate::from_ron(b"(name: 1)"), Err(RoomTemplateError::Parse(_))));
+    }
+}
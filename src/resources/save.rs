@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use crate::components::attack::WeaponType;
+use crate::components::inventory::ItemStack;
+use crate::constants::*;
+
+/// 一局遊戲的存檔
+///
+/// 樓層本身不存：同一個種子與同一組房間樣板會生成完全相同的樓層，
+/// 存檔只記錄生成之後改變的部分（玩家、門、已清除的房間、已撿起的物品）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    pub seed: u64,
+    pub room_count: usize,            // 用來確認讀檔時生成的是同一個樓層
+    pub edge_count: usize,
+    pub current_room: Option<usize>,
+    pub player: PlayerSave,
+    pub open_doors: Vec<(i32, i32)>,      // 開啟中的門格子
+    pub unlocked_doors: Vec<(i32, i32)>,  // 已用鑰匙打開的上鎖門
+    pub cleared_rooms: Vec<usize>,
+    pub collected_items: Vec<(i32, i32)>,  // 已撿起的樓層物品所在的格子
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub position: (f32, f32),
+    pub health: i32,
+    pub max_health: i32,
+    pub weapon: WeaponType,
+    pub experience: u32,  // 累積經驗值，等級由等級表算出
+    pub inventory: Vec<ItemStack>,  // 物品欄（包含鑰匙）
+}
+
+/// 只讀版本號，先確認格式再解析其餘欄位
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// 等待套用的存檔 - 選擇繼續遊戲時插入，樓層生成完成進入 Playing 時套用後移除
+#[derive(Resource)]
+pub struct PendingRestore(pub SaveData);
+
+/// 存檔讀寫錯誤
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "無法讀寫存檔: {error}"),
+            SaveError::Parse(error) => write!(f, "存檔已損毀: {error}"),
+            SaveError::Serialize(error) => write!(f, "無法序列化存檔: {error}"),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "不支援的存檔版本 {version}（目前版本 {SAVE_VERSION}）")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(error: std::io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        SaveError::Parse(error)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(error: ron::Error) -> Self {
+        SaveError::Serialize(error)
+    }
+}
+
+impl SaveData {
+    pub fn from_ron(text: &str) -> Result<Self, SaveError> {
+        let header: SaveHeader = ron::from_str(text)?;
+        if header.version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(header.version));
+        }
+        Ok(ron::from_str(text)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+
+    /// 讀取存檔；檔案不存在回傳 None
+    pub fn read(path: &Path) -> Result<Option<Self>, SaveError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_ron(&text).map(Some),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// 先寫入暫存檔再改名，中途關閉遊戲也不會留下寫到一半的存檔
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, self.to_ron()?)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// 刪除存檔（一局結束後不能再繼續）
+    pub fn delete(path: &Path) -> Result<(), SaveError> {
+        match std::fs::remove_file(path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::inventory::ItemKind;
+
+    fn sample() -> SaveData {
+        SaveData {
+            version: SAVE_VERSION,
+            seed: 42,
+            room_count: 12,
+            edge_count: 14,
+            current_room: Some(3),
+            player: PlayerSave {
+                position: (120.0, -48.5),
+                health: 65,
+                max_health: 100,
+                weapon: WeaponType::Sword,
+                experience: 75,
+                inventory: vec![
+                    ItemStack { item: ItemKind::Weapon(WeaponType::Sword), count: 1 },
+                    ItemStack { item: ItemKind::Key, count: 2 },
+                    ItemStack { item: ItemKind::Gold, count: 37 },
+                ],
+            },
+            open_doors: vec![(31, 4), (-2, 64)],
+            unlocked_doors: vec![(31, 4)],
+            cleared_rooms: vec![0, 3, 5],
+            collected_items: vec![(12, -3)],
+        }
+    }
+
+    #[test]
+    fn save_round_trips_through_ron() {
+        let save = sample();
+        let text = save.to_ron().unwrap();
+        assert_eq!(SaveData::from_ron(&text).unwrap(), save);
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let text = sample().to_ron().unwrap()
+            .replace(&format!("version: {SAVE_VERSION}"), "version: 99");
+        assert!(matches!(SaveData::from_ron(&text), Err(SaveError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn corrupt_saves_are_errors() {
+        let text = sample().to_ron().unwrap();
+        assert!(matches!(SaveData::from_ron(&text[..text.len() / 2]), Err(SaveError::Parse(_))));
+        assert!(matches!(SaveData::from_ron(""), Err(SaveError::Parse(_))));
+    }
+
+    #[test]
+    fn write_then_read_and_delete() {
+        let path = std::env::temp_dir().join(format!("dungeon_of_rust_save_{}", std::process::id())).join("run.ron");
+        let save = sample();
+
+        save.write(&path).unwrap();
+        assert_eq!(SaveData::read(&path).unwrap(), Some(save));
+
+        SaveData::delete(&path).unwrap();
+        assert!(SaveData::read(&path).unwrap().is_none());
+        SaveData::delete(&path).unwrap();
+        let _ = std::fs::remove_dir(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const SEED_ARG: &str = "--seed";
+const SEED_ENV: &str = "DUNGEON_SEED";
+
+/// 地城種子 - 所有隨機決策都由它衍生，同一個種子會生成完全相同的樓層
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DungeonSeed(pub u64);
+
+/// 各用途的獨立亂數流，避免新增一種隨機決策就改變其他決策的結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    FloorLayout,  // 房間選擇、尺寸、門與走廊
+    EnemySpawn,   // 敵人種類與位置
+    Runtime,      // 遊戲進行中的隨機決策（敵人巡邏等）
+    ItemSpawn,    // 房間內放置的物品
+}
+
+impl RngStream {
+    fn salt(&self) -> u64 {
+        match self {
+            RngStream::FloorLayout => 0x9E37_79B9_7F4A_7C15,
+            RngStream::EnemySpawn => 0xBF58_476D_1CE4_E5B9,
+            RngStream::Runtime => 0x94D0_49BB_1331_11EB,
+            RngStream::ItemSpawn => 0xD6E8_FEB8_6659_FD93,
+        }
+    }
+}
+
+impl DungeonSeed {
+    /// 依序讀取 `--seed <n>` / `--seed=<n>` 參數與 DUNGEON_SEED 環境變數，都沒有就回傳 None
+    pub fn from_args_or_env() -> Option<Self> {
+        let from_args = Self::parse_args(std::env::args().skip(1));
+        let from_env = std::env::var(SEED_ENV).ok().and_then(|value| Self::parse(&value, SEED_ENV));
+
+        from_args.or(from_env)
+    }
+
+    fn parse_args(args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            if arg == SEED_ARG {
+                return args.next().and_then(|value| Self::parse(&value, SEED_ARG));
+            }
+            if let Some(value) = arg.strip_prefix(SEED_ARG).and_then(|rest| rest.strip_prefix('=')) {
+                return Self::parse(value, SEED_ARG);
+            }
+        }
+        None
+    }
+
+    fn parse(value: &str, source: &str) -> Option<Self> {
+        match value.trim().parse() {
+            Ok(seed) => Some(Self(seed)),
+            Err(_) => {
+                warn!("無效的種子 {:?}（來自 {}），改用隨機種子", value, source);
+                None
+            }
+        }
+    }
+
+    /// 取得指定用途的亂數產生器
+    pub fn rng(&self, stream: RngStream) -> StdRng {
+        StdRng::seed_from_u64(self.0 ^ stream.salt())
+    }
+}
+
+/// 固定的種子 - 由 --seed 或 DUNGEON_SEED 指定時每一局都使用同一個種子，否則每一局重新隨機
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct PinnedSeed(pub Option<DungeonSeed>);
+
+impl PinnedSeed {
+    /// 新的一局使用的種子
+    pub fn next_run_seed(&self) -> DungeonSeed {
+        self.0.unwrap_or_else(|| DungeonSeed(rand::random()))
+    }
+}
+
+/// 遊戲進行中使用的亂數產生器（由 DungeonSeed 的 Runtime 亂數流建立）
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
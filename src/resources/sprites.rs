@@ -1,8 +1,61 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
-use crate::components::movement::Direction;
+use crate::components::attack::{WeaponSprites, WeaponType};
+use crate::resources::{AssetManifest, CharacterMetadata, WeaponCatalog, WeaponAttack};
 
+/// 視覺化戰鬥使用的武器圖片（依武器設定表載入）
 #[derive(Resource)]
-pub struct DirectionSpriteHandles {
-    pub handles: HashMap<Direction, Handle<Image>>,
-}
\ No newline at end of file
+pub struct CombatSprites {
+    pub weapons: HashMap<WeaponType, (Handle<Image>, Option<Handle<Image>>)>,  // (右側/默認圖片, 左側圖片)
+    pub impacts: HashMap<WeaponType, Handle<Image>>,  // 投射物命中效果，只有設定了圖片的武器才有
+}
+
+impl CombatSprites {
+    pub fn load_all(asset_server: &AssetServer, manifest: &mut AssetManifest, catalog: &WeaponCatalog) -> Self {
+        let weapons = catalog.weapons.iter()
+            .map(|(weapon, definition)| {
+                let right = manifest.load(asset_server, definition.sprite);
+                let left = definition.sprite_left.map(|path| manifest.load(asset_server, path));
+                (*weapon, (right, left))
+            })
+            .collect();
+
+        // 沒有設定命中效果圖片的武器用色塊代替
+        let impacts = catalog.weapons.iter()
+            .filter_map(|(weapon, definition)| match &definition.attack {
+                WeaponAttack::Projectile(projectile) => Some((*weapon, projectile.impact.sprite?)),
+                WeaponAttack::Melee { .. } => None,
+            })
+            .map(|(weapon, path)| (weapon, manifest.load(asset_server, path)))
+            .collect();
+
+        Self { weapons, impacts }
+    }
+
+    pub fn weapon(&self, weapon: WeaponType) -> WeaponSprites {
+        let (right, left) = self.weapons.get(&weapon).cloned().unwrap_or_default();
+        WeaponSprites { right_sprite: right, left_sprite: left }
+    }
+}
+
+/// 所有角色的描述檔（以 assets/characters 底下的資料夾名稱索引）
+#[derive(Resource)]
+pub struct CharacterMetadataHandles {
+    pub handles: HashMap<&'static str, Handle<CharacterMetadata>>,
+}
+
+impl CharacterMetadataHandles {
+    pub const CHARACTERS: [&'static str; 5] = ["knight_hero", "knight_lv2", "knight_lv3", "green_slime", "skeleton_warrior"];
+
+    pub fn load_all(asset_server: &AssetServer, manifest: &mut AssetManifest) -> Self {
+        let handles = Self::CHARACTERS.into_iter()
+            .map(|name| (name, manifest.load(asset_server, &format!("characters/{name}/metadata.json"))))
+            .collect();
+
+        Self { handles }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Handle<CharacterMetadata>> {
+        self.handles.get(name)
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use crate::components::attack::WeaponType;
+use crate::components::status::StatusKind;
+use crate::constants::*;
+
+/// 武器設定表 - 每種武器的圖片、揮擊、冷卻、傷害與攻擊方式
+///
+/// 生成與切換武器都只讀這份設定，調整武器不需要改戰鬥系統
+#[derive(Resource, Debug, Clone)]
+pub struct WeaponCatalog {
+    pub weapons: HashMap<WeaponType, WeaponDefinition>,
+}
+
+/// 一種武器的設定
+#[derive(Debug, Clone)]
+pub struct WeaponDefinition {
+    pub sprite: &'static str,               // 右側/默認圖片
+    pub sprite_left: Option<&'static str>,  // 左側圖片；沒有時翻轉默認圖片
+    pub aim: bool,                          // 圖片是否朝面向旋轉（否則直立拿著）
+    pub scale: f32,                         // 武器實體縮放（會再乘上玩家的縮放）
+    pub grip: f32,                          // 圖片中心沿面向離手的距離（玩家本地座標）
+    pub swing_arc: (f32, f32),              // 揮擊的起始與結束角度（弧度，相對於基礎角度）
+    pub swing_duration: f32,
+    pub cooldown: f32,                      // 兩次攻擊的最短間隔
+    pub damage_multiplier: f32,             // 乘上玩家的攻擊力
+    pub on_hit: Option<StatusKind>,         // 命中時附加的狀態效果
+    pub attack: WeaponAttack,
+}
+
+/// 武器的攻擊方式
+#[derive(Debug, Clone)]
+pub enum WeaponAttack {
+    /// 揮擊時武器上的判定框命中目標
+    Melee { reach: f32, radius: f32, knockback: f32 },
+    /// 揮擊開始時從手上朝面向發射投射物
+    Projectile(ProjectileDefinition),
+}
+
+/// 投射物的設定
+#[derive(Debug, Clone)]
+pub struct ProjectileDefinition {
+    pub speed: f32,
+    pub lifetime: f32,
+    pub radius: f32,        // 命中半徑（像素）
+    pub knockback: f32,
+    pub size: Vec2,         // 投射物大小（還沒有投射物圖片）
+    pub color: Color,
+    pub count: u32,         // 一次發射幾個
+    pub spread: f32,        // 多個投射物平均分布的總角度（弧度）
+    pub pierce: u32,        // 可以穿過幾個目標，之後命中就消失
+    pub bounces: u32,       // 撞牆可以反彈幾次，之後撞牆就消失
+    pub homing: f32,        // 每秒最多轉向幾弧度追蹤最近的目標（0 不追蹤）
+    pub impact: ImpactDefinition,
+}
+
+/// 投射物命中目標或撞牆時的效果
+#[derive(Debug, Clone)]
+pub struct ImpactDefinition {
+    pub sprite: Option<&'static str>,  // 效果圖片；沒有時用色塊代替
+    pub size: Vec2,
+    pub color: Color,
+    pub duration: f32,
+}
+
+impl WeaponCatalog {
+    pub fn get(&self, weapon: WeaponType) -> &WeaponDefinition {
+        &self.weapons[&weapon]
+    }
+}
+
+impl Default for WeaponCatalog {
+    /// 劍：近距離大範圍揮砍，命中暈眩；法杖：慢速的追蹤魔法球，命中燃燒；弓：快速的扇形箭，命中緩速
+    fn default() -> Self {
+        let weapons = HashMap::from([
+            (WeaponType::Sword, WeaponDefinition {
+                sprite: "sprites/weapons/sword.png",
+                sprite_left: Some("sprites/weapons/sword_left.png"),
+                aim: true,
+                scale: 0.8,
+                grip: SWORD_GRIP_OFFSET,
+                swing_arc: (-PI / 4.0, PI / 4.0),
+                swing_duration: 0.5,
+                cooldown: 0.5,
+                damage_multiplier: 1.0,
+                on_hit: Some(StatusKind::Stun),
+                attack: WeaponAttack::Melee {
+                    reach: SWORD_HITBOX_REACH,
+                    radius: SWORD_HITBOX_RADIUS,
+                    knockback: SWORD_KNOCKBACK,
+                },
+            }),
+            (WeaponType::Magic, WeaponDefinition {
+                sprite: "sprites/weapons/staff.png",
+                sprite_left: None,
+                aim: false,
+                scale: 0.35,
+                grip: 4.0,
+                // 法杖往前點一下
+                swing_arc: (PI / 6.0, -PI / 12.0),
+                swing_duration: 0.35,
+                cooldown: 0.8,
+                damage_multiplier: 1.4,
+                on_hit: Some(StatusKind::Burn),
+                // 會追蹤敵人、撞牆反彈一次的魔法球
+                attack: WeaponAttack::Projectile(ProjectileDefinition {
+                    speed: 260.0,
+                    lifetime: 1.6,
+                    radius: 14.0,
+                    knockback: 250.0,
+                    size: Vec2::splat(18.0),
+                    color: Color::srgb(0.5, 0.6, 1.0),
+                    count: 1,
+                    spread: 0.0,
+                    pierce: 0,
+                    bounces: 1,
+                    homing: 3.0,
+                    impact: ImpactDefinition {
+                        sprite: None,  // 規劃中的 effects/magic_explosion.png
+                        size: Vec2::splat(40.0),
+                        color: Color::srgb(0.6, 0.7, 1.0),
+                        duration: 0.3,
+                    },
+                }),
+            }),
+            (WeaponType::Arrow, WeaponDefinition {
+                sprite: "sprites/weapons/bow.png",
+                sprite_left: None,
+                aim: false,
+                scale: 0.35,
+                grip: 4.0,
+                // 拉弓時往後微微一晃
+                swing_arc: (-PI / 16.0, 0.0),
+                swing_duration: 0.25,
+                cooldown: 0.4,
+                damage_multiplier: 0.5,
+                on_hit: Some(StatusKind::Slow),
+                // 扇形射出三支可以穿過一個敵人的箭
+                attack: WeaponAttack::Projectile(ProjectileDefinition {
+                    speed: 480.0,
+                    lifetime: 1.2,
+                    radius: 8.0,
+                    knockback: 150.0,
+                    size: Vec2::new(20.0, 4.0),
+                    color: Color::srgb(0.6, 0.3, 0.1),
+                    count: 3,
+                    spread: PI / 6.0,
+                    pierce: 1,
+                    bounces: 0,
+                    homing: 0.0,
+                    impact: ImpactDefinition {
+                        sprite: None,  // 規劃中的 effects/arrow_hit.png
+                        size: Vec2::splat(16.0),
+                        color: Color::srgb(0.8, 0.7, 0.5),
+                        duration: 0.15,
+                    },
+                }),
+            }),
+        ]);
+
+        Self { weapons }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_weapon_type_has_a_definition() {
+        let catalog = WeaponCatalog::default();
+        for weapon in WeaponType::ALL {
+            let definition = catalog.get(weapon);
+            assert!(definition.cooldown > 0.0 && definition.swing_duration > 0.0);
+            assert!(definition.damage_multiplier > 0.0);
+        }
+        assert!(matches!(catalog.get(WeaponType::Sword).attack, WeaponAttack::Melee { .. }));
+        assert!(matches!(catalog.get(WeaponType::Arrow).attack, WeaponAttack::Projectile(ref projectile) if projectile.count > 0));
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+/// 遊戲流程狀態
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,  // 主選單
+    Loading,   // 載入資源並生成樓層
+    Playing,   // 遊戲進行中
+    Paused,    // 暫停
+    GameOver,  // 玩家死亡
+    Victory,   // 清除所有敵人
+}
+
+/// 一局遊戲進行中（離開主選單到回到主選單之間）
+///
+/// 樓層、玩家與敵人都以 `StateScoped(InRun)` 生成，回到主選單時自動清除
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InRun;
+
+impl ComputedStates for InRun {
+    type SourceStates = AppState;
+
+    fn compute(sources: AppState) -> Option<Self> {
+        match sources {
+            AppState::MainMenu => None,
+            _ => Some(InRun),
+        }
+    }
+}
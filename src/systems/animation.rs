@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use crate::components::player::{Player, InputVector, Health, Velocity};
+use crate::components::enemy::{Enemy, EnemyAi, EnemyState};
+use crate::components::attack::{PlayerFacing, Knockback, Weapon, WeaponSwingAnimation};
+use crate::components::animation::{AnimationKind, CharacterAnimation};
+use crate::components::movement::Direction;
+use crate::resources::CharacterMetadata;
+use crate::constants::*;
+
+/// 玩家動畫狀態：死亡 > 受傷（擊退中）> 攻擊（揮劍中）> 行走 > 待機
+#[allow(clippy::type_complexity)]
+pub fn player_animation_state_system(
+    mut player_query: Query<(&mut CharacterAnimation, &InputVector, &PlayerFacing, &Health, Has<Knockback>), With<Player>>,
+    weapon_query: Query<&WeaponSwingAnimation, With<Weapon>>,
+) {
+    let attacking = weapon_query.iter().any(|swing| swing.is_attacking);
+
+    for (mut animation, input, facing, health, knocked_back) in &mut player_query {
+        let kind = animation_kind(health, knocked_back, attacking, input.0.length() > INPUT_DEADZONE);
+        let direction = Direction::from_input_vector(facing.direction).unwrap_or(animation.direction);
+        animation.set(kind, direction);
+    }
+}
+
+/// 敵人動畫狀態：依 AI 狀態與速度決定動畫，攻擊時面向玩家、其他時候面向移動方向
+#[allow(clippy::type_complexity)]
+pub fn enemy_animation_state_system(
+    mut enemy_query: Query<(&mut CharacterAnimation, &EnemyAi, &Velocity, &Health, &Transform, Has<Knockback>), With<Enemy>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let player_pos = player_query.single().ok().map(|transform| transform.translation.truncate());
+
+    for (mut animation, ai, velocity, health, transform, knocked_back) in &mut enemy_query {
+        let attacking = ai.state == EnemyState::Attack;
+        let kind = animation_kind(health, knocked_back, attacking, velocity.0.length() > ENEMY_ANIMATION_MIN_SPEED);
+
+        let facing = match player_pos {
+            Some(player_pos) if attacking => player_pos - transform.translation.truncate(),
+            _ => velocity.0,
+        };
+        let direction = Direction::from_input_vector(facing.normalize_or_zero()).unwrap_or(animation.direction);
+        animation.set(kind, direction);
+    }
+}
+
+fn animation_kind(health: &Health, knocked_back: bool, attacking: bool, moving: bool) -> AnimationKind {
+    if health.current <= 0 {
+        AnimationKind::Death
+    } else if knocked_back {
+        AnimationKind::Hurt
+    } else if attacking {
+        AnimationKind::Attack
+    } else if moving {
+        AnimationKind::Walk
+    } else {
+        AnimationKind::Idle
+    }
+}
+
+/// 動畫播放系統 - 依每秒影格數推進影格並更新 Sprite 在圖集上的索引
+pub fn animate_characters_system(
+    mut query: Query<(&mut CharacterAnimation, &mut Sprite)>,
+    metadata_assets: Res<Assets<CharacterMetadata>>,
+    time: Res<Time>,
+) {
+    for (mut animation, mut sprite) in &mut query {
+        let Some(metadata) = metadata_assets.get(&animation.metadata) else {
+            continue;
+        };
+        let frames = metadata.frames(animation.kind, animation.direction);
+        if frames.is_empty() {
+            continue;
+        }
+
+        animation.timer.tick(time.delta());
+        let advanced = animation.frame + animation.timer.times_finished_this_tick() as usize;
+        animation.frame = if animation.kind.looping() {
+            advanced % frames.len()
+        } else {
+            advanced.min(frames.len() - 1)
+        };
+
+        // 換方向時新的動畫可能比較短
+        let index = frames[animation.frame.min(frames.len() - 1)];
+        if let Some(atlas) = sprite.texture_atlas.as_mut()
+            && atlas.index != index
+        {
+            atlas.index = index;
+        }
+    }
+}
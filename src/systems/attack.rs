@@ -1,429 +1,436 @@
 use bevy::prelude::*;
-use crate::components::player::{Player, AttackCooldown, AttackDamage, InputVector, CurrentWeapon};
-use crate::components::movement::Direction;
+use crate::components::player::{Player, AttackDamage, CurrentWeapon, InputVector, Velocity};
+use crate::components::enemy::Enemy;
+use crate::components::inventory::{Inventory, ItemKind};
+use crate::components::camera::CameraImpulseEvent;
+use crate::components::status::{StatusEffects, ApplyStatusEvent};
 use crate::components::attack::{
-    AttackEvent, AttackAnimation, Projectile, AttackType, MeleeAttack, WeaponType, 
-    WeaponEffect, CombatState, TimingAttack, AttackStage, AttackTiming, WeaponSelector
+    CombatState, TimingAttack, AttackStage, AttackTiming, WeaponSelector, TimingOpponent,
+    Weapon, WeaponSwingAnimation, WeaponType, Hitbox, Hurtbox, PlayerFacing, DamageEvent, DamageSource,
 };
+use crate::resources::{ActionState, InputAction, InputBindings, WeaponCatalog, WeaponAttack};
+use crate::systems::projectile::spawn_projectiles;
+use crate::states::InRun;
+use crate::constants::*;
 
-pub fn attack_input_system(
-    mut attack_input_events: EventReader<crate::systems::input::AttackInputEvent>,
-    mut attack_events: EventWriter<AttackEvent>,
-    mut query: Query<(Entity, &Transform, &mut AttackCooldown, &AttackDamage, &Direction, &InputVector, &mut AttackAnimation, &CurrentWeapon), With<Player>>,
-    time: Res<Time>,
+// 戰鬥狀態管理系統 (參考 GitHub 專案) - 按時機攻擊鍵與附近最近的敵人進入時機攻擊模式
+#[allow(clippy::type_complexity)]
+pub fn combat_state_system(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    mut next_state: ResMut<NextState<CombatState>>,
+    mut player_query: Query<(Entity, &Transform, &CurrentWeapon, &Inventory, &mut InputVector, &mut Velocity), With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Velocity), (With<Enemy>, With<Hurtbox>, Without<Player>)>,
 ) {
-    // 每幀檢查是否有玩家實體
-    if query.is_empty() {
-        return; // 沒有玩家實體就退出
-    }
-    
-    // 更新所有玩家的攻擊冷卻時間
-    for (_entity, transform, mut cooldown, damage, direction, input_vector, mut animation, current_weapon) in &mut query {
-        cooldown.timer.tick(time.delta());
+    if !action_state.just_pressed(InputAction::TimingAttack) {
+        return;
     }
-    
-    // 檢查攻擊事件
-    for _event in attack_input_events.read() {
-        for (_entity, transform, mut cooldown, damage, direction, input_vector, mut animation, current_weapon) in &mut query {
-            info!("收到攻擊輸入事件！冷卻狀態: {}", cooldown.timer.finished());
-            if cooldown.timer.finished() {
-                info!("攻擊觸發！");
-                // 決定攻擊方向：如果有移動輸入就用移動方向，否則用面向方向
-                let attack_direction = if input_vector.0.length() > 0.1 {
-                    input_vector.0.normalize()
-                } else {
-                    direction.to_vec2()
-                };
-
-                // 發送攻擊事件
-                attack_events.write(AttackEvent {
-                    position: transform.translation,
-                    direction: attack_direction,
-                    damage: damage.0,
-                    weapon_type: current_weapon.weapon_type,
-                    timing: None, // 基礎攻擊沒有時機判定
-                });
+    let Ok((player_entity, player_transform, current_weapon, inventory, mut input, mut velocity)) = player_query.single_mut() else {
+        return;
+    };
 
-                // 開始攻擊動畫
-                animation.is_attacking = true;
-                animation.timer.reset();
+    let player_pos = player_transform.translation.truncate();
+    let opponent = enemy_query.iter()
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate().distance(player_pos)))
+        .filter(|(_, distance)| *distance <= TIMING_ENGAGE_RANGE)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((opponent, _)) = opponent else {
+        info!("附近沒有可以時機攻擊的敵人");
+        return;
+    };
 
-                // 重新開始冷卻計時
-                cooldown.timer = Timer::from_seconds(0.5, TimerMode::Once);
-            }
-        }
+    // 即時的移動在時機攻擊模式中暫停，先讓所有人停下來
+    input.0 = Vec2::ZERO;
+    velocity.0 = Vec2::ZERO;
+    for (_, _, mut enemy_velocity) in &mut enemy_query {
+        enemy_velocity.0 = Vec2::ZERO;
     }
+
+    // 可以選擇物品欄中的武器
+    let available_weapons: Vec<WeaponType> = WeaponType::ALL.into_iter()
+        .filter(|weapon| inventory.count(ItemKind::Weapon(*weapon)) > 0)
+        .collect();
+    let selected_index = available_weapons.iter()
+        .position(|weapon| *weapon == current_weapon.weapon_type)
+        .unwrap_or(0);
+    commands.entity(player_entity).insert((
+        TimingOpponent(opponent),
+        WeaponSelector { available_weapons, selected_index },
+    ));
+
+    next_state.set(CombatState::PlayerSelecting);
+    info!("進入時機攻擊模式！");
 }
 
-pub fn handle_attack_events(
+// 武器選擇系統 - 切換武器鍵選擇武器，攻擊鍵開始出手，時機攻擊鍵離開
+pub fn weapon_selection_system(
     mut commands: Commands,
-    mut attack_events: EventReader<AttackEvent>,
-    asset_server: Res<AssetServer>,
+    action_state: Res<ActionState>,
+    mut next_state: ResMut<NextState<CombatState>>,
+    mut player_query: Query<(Entity, &mut WeaponSelector, &mut CurrentWeapon, &TimingOpponent), With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
 ) {
-    for event in attack_events.read() {
-        info!("生成武器攻擊效果 - 位置: {:?}, 方向: {:?}, 武器: {:?}", 
-              event.position, event.direction, event.weapon_type);
-        
-        match event.weapon_type {
-            WeaponType::Sword => {
-                spawn_sword_effect(&mut commands, &asset_server, event);
-            }
-            WeaponType::Magic => {
-                spawn_magic_effect(&mut commands, &asset_server, event);
-            }
-            WeaponType::Arrow => {
-                spawn_arrow_projectile(&mut commands, &asset_server, event);
-            }
-        }
+    let Ok((player_entity, mut selector, mut current_weapon, opponent)) = player_query.single_mut() else {
+        return;
+    };
+
+    // 對手已經被其他攻擊打倒
+    if !enemy_query.contains(opponent.0) || action_state.just_pressed(InputAction::TimingAttack) {
+        next_state.set(CombatState::Idle);
+        return;
     }
-}
 
-fn spawn_sword_effect(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    event: &AttackEvent,
-) {
-    let attack_offset = event.direction * 50.0;
-    
-    // Try to load sword swing sprite, fall back to colored rectangle if not found
-    let sprite_bundle = if std::path::Path::new("assets/weapons/sword_swing.png").exists() {
-        Sprite::from_image(asset_server.load("weapons/sword_swing.png"))
-    } else {
-        // Fallback to colored sprite with sword-like shape
-        Sprite::from_color(Color::srgb(1.0, 0.8, 0.0), Vec2::new(80.0, 30.0))
-    };
-    
-    commands.spawn((
-        sprite_bundle,
-        Transform::from_translation(event.position + attack_offset.extend(2.0))
-            .with_rotation(Quat::from_rotation_z(event.direction.y.atan2(event.direction.x))),
-        WeaponEffect {
-            lifetime: Timer::from_seconds(0.3, TimerMode::Once),
-            weapon_type: WeaponType::Sword,
-            scale_animation: true,
-        },
-        MeleeAttack {
-            lifetime: Timer::from_seconds(0.3, TimerMode::Once),
-            damage: event.damage,
-        },
-        AttackType::Melee,
-    ));
+    if action_state.just_pressed(InputAction::SwitchWeapon) && !selector.available_weapons.is_empty() {
+        selector.selected_index = (selector.selected_index + 1) % selector.available_weapons.len();
+        current_weapon.weapon_type = selector.available_weapons[selector.selected_index];
+    }
+
+    if action_state.just_pressed(InputAction::Attack) {
+        commands.entity(player_entity).insert(TimingAttack::default());
+        next_state.set(CombatState::PlayerAttacking);
+        info!("開始時機攻擊 - 武器: {:?}", current_weapon.weapon_type);
+    }
 }
 
-fn spawn_magic_effect(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    event: &AttackEvent,
+// 時機攻擊系統 (參考 GitHub 專案) - 玩家在行動階段按下攻擊鍵，依時機決定傷害倍數
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn timing_attack_system(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    mut next_state: ResMut<NextState<CombatState>>,
+    mut player_query: Query<(Entity, &mut TimingAttack, &Transform, &mut PlayerFacing, &AttackDamage, &StatusEffects, &TimingOpponent), With<Player>>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<Player>)>,
+    mut weapon_query: Query<(&Weapon, &mut WeaponSwingAnimation, &Transform, Option<&mut Hitbox>), (Without<Player>, Without<Enemy>)>,
+    catalog: Res<WeaponCatalog>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+    time: Res<Time>,
 ) {
-    let projectile_offset = event.direction * 30.0;
-    
-    let sprite_bundle = if std::path::Path::new("assets/weapons/magic_fireball.png").exists() {
-        Sprite::from_image(asset_server.load("weapons/magic_fireball.png"))
-    } else {
-        // Fallback to orange circle
-        Sprite::from_color(Color::srgb(1.0, 0.5, 0.0), Vec2::new(24.0, 24.0))
+    let Ok((player_entity, mut timing_attack, player_transform, mut facing, damage, effects, opponent)) = player_query.single_mut() else {
+        return;
     };
-    
-    commands.spawn((
-        sprite_bundle,
-        Transform::from_translation(event.position + projectile_offset.extend(1.0)),
-        Projectile {
-            velocity: event.direction * 200.0,
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-            damage: event.damage,
-        },
-        WeaponEffect {
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-            weapon_type: WeaponType::Magic,
-            scale_animation: false,
+
+    match timing_attack.stage {
+        AttackStage::Warmup => {
+            timing_attack.warmup_timer.tick(time.delta());
+            if timing_attack.warmup_timer.finished() {
+                timing_attack.stage = AttackStage::Action;
+                info!("進入行動階段！按攻擊鍵攻擊！");
+            }
         },
-        AttackType::Ranged,
-    ));
-}
+        AttackStage::Action => {
+            timing_attack.action_timer.tick(time.delta());
 
-fn spawn_arrow_projectile(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    event: &AttackEvent,
-) {
-    let projectile_offset = event.direction * 30.0;
-    
-    let sprite_bundle = if std::path::Path::new("assets/weapons/arrow.png").exists() {
-        Sprite::from_image(asset_server.load("weapons/arrow.png"))
-    } else {
-        // Fallback to brown rectangle
-        Sprite::from_color(Color::srgb(0.6, 0.3, 0.1), Vec2::new(20.0, 4.0))
-    };
-    
-    commands.spawn((
-        sprite_bundle,
-        Transform::from_translation(event.position + projectile_offset.extend(1.0))
-            .with_rotation(Quat::from_rotation_z(event.direction.y.atan2(event.direction.x))),
-        Projectile {
-            velocity: event.direction * 300.0,
-            lifetime: Timer::from_seconds(3.0, TimerMode::Once),
-            damage: event.damage,
+            // 檢查時機輸入；時間到了還沒按，算作差時機
+            let pressed = action_state.just_pressed(InputAction::Attack);
+            if !pressed && !timing_attack.action_timer.finished() {
+                return;
+            }
+            if pressed {
+                timing_attack.timing = Some(AttackTiming::from_ratio(timing_attack.action_progress()));
+            }
+            timing_attack.damage_multiplier = AttackTiming::attack_multiplier(timing_attack.timing);
+            timing_attack.stage = AttackStage::CoolDown;
+            info!("攻擊執行！時機: {:?}, 傷害倍數: {:.1}", timing_attack.timing, timing_attack.damage_multiplier);
+
+            let Ok(target_transform) = enemy_query.get(opponent.0) else {
+                return;
+            };
+            let player_pos = player_transform.translation.truncate();
+            let target_pos = target_transform.translation.truncate();
+            let direction = (target_pos - player_pos).normalize_or(facing.direction);
+            facing.direction = direction;
+
+            for (weapon, mut swing, weapon_transform, hitbox) in &mut weapon_query {
+                let definition = catalog.get(weapon.weapon_type);
+                let multiplier = definition.damage_multiplier * timing_attack.damage_multiplier * effects.damage_multiplier();
+                let amount = (damage.0 as f32 * multiplier).round() as i32;
+                swing.is_attacking = true;
+                swing.timer.reset();
+
+                match &definition.attack {
+                    // 近戰直接命中對手；揮擊判定框先記下對手，避免同一次揮擊再命中一次
+                    WeaponAttack::Melee { knockback, .. } => {
+                        if let Some(mut hitbox) = hitbox {
+                            hitbox.hit_entities.insert(opponent.0);
+                        }
+                        damage_events.write(DamageEvent {
+                            attacker: player_entity,
+                            target: opponent.0,
+                            amount,
+                            knockback: direction * *knockback,
+                            source: DamageSource::Hit,
+                        });
+                        if let Some(kind) = definition.on_hit {
+                            status_events.write(ApplyStatusEvent { target: opponent.0, kind });
+                        }
+                    }
+                    // 遠程武器朝對手發射，投射物帶著時機倍數後的傷害
+                    WeaponAttack::Projectile(_) => {
+                        let origin = player_transform.mul_transform(*weapon_transform).translation.truncate();
+                        spawn_projectiles(&mut commands, player_entity, origin, direction, amount, weapon.weapon_type, definition);
+                    }
+                }
+            }
         },
-        WeaponEffect {
-            lifetime: Timer::from_seconds(3.0, TimerMode::Once),
-            weapon_type: WeaponType::Arrow,
-            scale_animation: false,
+        AttackStage::CoolDown => {
+            timing_attack.cooldown_timer.tick(time.delta());
+            if timing_attack.cooldown_timer.finished() {
+                // 換敵人出手；對手已被打倒就回到即時戰鬥
+                commands.entity(player_entity).remove::<TimingAttack>();
+                if enemy_query.contains(opponent.0) {
+                    commands.entity(opponent.0).insert(TimingAttack::default());
+                    next_state.set(CombatState::EnemyAttacking);
+                } else {
+                    next_state.set(CombatState::Idle);
+                    info!("攻擊完成，回到空閒狀態");
+                }
+            }
         },
-        AttackType::Ranged,
-    ));
+    }
 }
 
-
-pub fn update_melee_attacks(
+// 敵人回合 - 敵人預備後出手，玩家在行動階段按攻擊鍵格擋，依時機減少受到的傷害；暈眩時跳過回合
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn enemy_timing_attack_system(
     mut commands: Commands,
-    mut melee_query: Query<(Entity, &mut MeleeAttack)>,
+    action_state: Res<ActionState>,
+    mut next_state: ResMut<NextState<CombatState>>,
+    player_query: Query<(Entity, &Transform, &TimingOpponent), With<Player>>,
+    mut enemy_query: Query<(&Enemy, Option<&mut TimingAttack>, &Transform, &AttackDamage, &StatusEffects), Without<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut impulse_events: EventWriter<CameraImpulseEvent>,
     time: Res<Time>,
 ) {
-    for (entity, mut melee_attack) in &mut melee_query {
-        // 更新生命週期
-        melee_attack.lifetime.tick(time.delta());
+    let Ok((player_entity, player_transform, opponent)) = player_query.single() else {
+        return;
+    };
+    let Ok((enemy, timing_attack, enemy_transform, damage, effects)) = enemy_query.get_mut(opponent.0) else {
+        // 對手已被移除（例如被還在飛的投射物打倒）
+        next_state.set(CombatState::Idle);
+        return;
+    };
+    let Some(mut timing_attack) = timing_attack else {
+        return;
+    };
 
-        // 如果超時就刪除攻擊效果
-        if melee_attack.lifetime.finished() {
-            commands.entity(entity).despawn();
-        }
+    // 暈眩的敵人不能出手，跳過這個回合
+    if effects.is_stunned() {
+        commands.entity(opponent.0).remove::<TimingAttack>();
+        next_state.set(CombatState::PlayerSelecting);
+        info!("{:?} 暈眩中，跳過回合", enemy.kind);
+        return;
     }
-}
 
-pub fn update_attack_animations(
-    mut query: Query<(&mut AttackAnimation, &mut Sprite)>,
-    time: Res<Time>,
-) {
-    for (mut animation, mut sprite) in &mut query {
-        if animation.is_attacking {
-            animation.timer.tick(time.delta());
-            
-            // 簡單的攻擊動畫效果：改變顏色
-            if animation.timer.finished() {
-                animation.is_attacking = false;
-                // 恢復原始顏色
-                sprite.color = Color::WHITE;
-            } else {
-                // 攻擊時微微變紅
-                sprite.color = Color::srgb(1.0, 0.8, 0.8);
+    match timing_attack.stage {
+        AttackStage::Warmup => {
+            timing_attack.warmup_timer.tick(time.delta());
+            if timing_attack.warmup_timer.finished() {
+                timing_attack.stage = AttackStage::Action;
+                info!("{:?} 出手！按攻擊鍵格擋！", enemy.kind);
             }
-        }
-    }
-}
+        },
+        AttackStage::Action => {
+            timing_attack.action_timer.tick(time.delta());
 
-// 新增：更新武器效果動畫
-pub fn update_weapon_effects(
-    mut commands: Commands,
-    mut weapon_query: Query<(Entity, &mut WeaponEffect, &mut Transform, &mut Sprite)>,
-    time: Res<Time>,
-) {
-    for (entity, mut effect, mut transform, mut sprite) in &mut weapon_query {
-        effect.lifetime.tick(time.delta());
-        
-        if effect.lifetime.finished() {
-            commands.entity(entity).despawn();
-            continue;
-        }
-        
-        // 根據武器類型播放不同動畫
-        match effect.weapon_type {
-            WeaponType::Sword => {
-                if effect.scale_animation {
-                    // 劍氣漸漸縮小並變透明
-                    let progress = effect.lifetime.elapsed_secs() / effect.lifetime.duration().as_secs_f32();
-                    let scale = 1.0 + (1.0 - progress) * 0.5; // 從1.5倍縮小到1.0倍
-                    transform.scale = Vec3::splat(scale);
-                    sprite.color.set_alpha(1.0 - progress);
-                }
+            let pressed = action_state.just_pressed(InputAction::Attack);
+            if !pressed && !timing_attack.action_timer.finished() {
+                return;
             }
-            WeaponType::Magic => {
-                // 魔法效果：輕微旋轉和閃爍
-                transform.rotate_z(time.delta_secs() * 2.0);
-                let flicker = (time.elapsed_secs() * 10.0).sin().abs();
-                sprite.color.set_alpha(0.7 + flicker * 0.3);
+            if pressed {
+                timing_attack.timing = Some(AttackTiming::from_ratio(timing_attack.action_progress()));
             }
-            WeaponType::Arrow => {
-                // 箭矢無特殊效果，保持穩定
+            timing_attack.damage_multiplier = AttackTiming::parry_multiplier(timing_attack.timing);
+            timing_attack.stage = AttackStage::CoolDown;
+
+            let amount = (damage.0 as f32 * timing_attack.damage_multiplier * effects.damage_multiplier()).round() as i32;
+            if amount > 0 {
+                let direction = (player_transform.translation - enemy_transform.translation).truncate().normalize_or_zero();
+                damage_events.write(DamageEvent {
+                    attacker: opponent.0,
+                    target: player_entity,
+                    amount,
+                    knockback: direction * ENEMY_KNOCKBACK,
+                    source: DamageSource::Hit,
+                });
+            } else {
+                impulse_events.write(CameraImpulseEvent::Shake { trauma: PARRY_TRAUMA });
             }
-        }
+            info!("格擋時機: {:?}, 受到 {} 傷害", timing_attack.timing, amount);
+        },
+        AttackStage::CoolDown => {
+            timing_attack.cooldown_timer.tick(time.delta());
+            if timing_attack.cooldown_timer.finished() {
+                // 回到玩家的回合
+                commands.entity(opponent.0).remove::<TimingAttack>();
+                next_state.set(CombatState::PlayerSelecting);
+            }
+        },
     }
 }
 
-// 新增：更新投射物移動
-pub fn update_projectiles(
-    mut projectile_query: Query<(&mut Transform, &Projectile)>,
-    time: Res<Time>,
+// 回到即時戰鬥時移除時機攻擊的組件
+#[allow(clippy::type_complexity)]
+pub fn end_timing_combat_system(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<TimingAttack>, With<TimingOpponent>, With<WeaponSelector>)>>,
 ) {
-    for (mut transform, projectile) in &mut projectile_query {
-        // 根據速度移動投射物
-        let movement = projectile.velocity * time.delta_secs();
-        transform.translation += movement.extend(0.0);
+    for entity in &query {
+        commands.entity(entity).remove::<(TimingAttack, TimingOpponent, WeaponSelector)>();
     }
 }
 
-// 新增：武器切換系統
-pub fn weapon_switching_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut CurrentWeapon, With<Player>>,
-) {
-    if let Ok(mut current_weapon) = query.single_mut() {
-        if keyboard_input.just_pressed(KeyCode::Digit1) {
-            current_weapon.weapon_type = WeaponType::Sword;
-            info!("切換到劍！");
-        } else if keyboard_input.just_pressed(KeyCode::Digit2) {
-            current_weapon.weapon_type = WeaponType::Magic;
-            info!("切換到魔法！");
-        } else if keyboard_input.just_pressed(KeyCode::Digit3) {
-            current_weapon.weapon_type = WeaponType::Arrow;
-            info!("切換到弓箭！");
-        }
-    }
-}
+// 時機條 UI - 畫面下方的說明文字與時機條，完美時機的區間標成金色
+#[derive(Component)]
+pub struct TimingBar;
 
-// 戰鬥狀態管理系統 (參考 GitHub 專案)
-pub fn combat_state_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    current_state: Res<State<CombatState>>,
-    mut next_state: ResMut<NextState<CombatState>>,
-    mut commands: Commands,
-    player_query: Query<Entity, With<Player>>,
-) {
-    match current_state.get() {
-        CombatState::Idle => {
-            // 按下 T 鍵進入戰鬥模式 (Timing Attack)
-            if keyboard_input.just_pressed(KeyCode::KeyT) {
-                info!("進入時機攻擊模式！");
-                next_state.set(CombatState::PlayerSelecting);
-                
-                // 為玩家添加武器選擇器
-                if let Ok(player_entity) = player_query.single() {
-                    commands.entity(player_entity).insert(WeaponSelector {
-                        available_weapons: vec![WeaponType::Sword, WeaponType::Magic, WeaponType::Arrow],
-                        selected_index: 0,
-                    });
-                }
-            }
-        },
-        CombatState::PlayerSelecting => {
-            // 在這個狀態下可以選擇武器和確認攻擊
-            // (具體實作在武器選擇系統中)
-        },
-        CombatState::PlayerAttacking => {
-            // 攻擊完成後回到空閒狀態
-            // (由時機攻擊系統管理)
-        },
-        CombatState::EnemyAttacking => {
-            // 敵人攻擊邏輯
-            // TODO: 實作敵人攻擊系統
+#[derive(Component)]
+pub struct TimingBarText;
+
+#[derive(Component)]
+pub struct TimingBarCursor;
+
+pub fn spawn_timing_bar_system(mut commands: Commands) {
+    let (width, height) = UI_TIMING_BAR_SIZE;
+    let (critical_start, critical_end) = TIMING_CRITICAL_WINDOW;
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(48.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.0),
+            ..default()
         },
-    }
+        Visibility::Hidden,
+        TimingBar,
+        StateScoped(InRun),
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new(""),
+            TextFont { font_size: UI_HUD_FONT_SIZE, ..default() },
+            TextColor(Color::WHITE),
+            TextLayout::new_with_justify(JustifyText::Center),
+            TimingBarText,
+        ));
+        parent.spawn((
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(height),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+        )).with_children(|bar| {
+            bar.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(critical_start * 100.0),
+                    width: Val::Percent((critical_end - critical_start) * 100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.9, 0.7, 0.2)),
+            ));
+            bar.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(0.0),
+                    width: Val::Px(4.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE),
+                TimingBarCursor,
+            ));
+        });
+    });
 }
 
-// 時機攻擊系統 (參考 GitHub 專案)
-pub fn timing_attack_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut commands: Commands,
-    current_state: Res<State<CombatState>>,
-    mut next_state: ResMut<NextState<CombatState>>,
-    mut timing_query: Query<(Entity, &mut TimingAttack, &Transform, &AttackDamage, &CurrentWeapon), With<Player>>,
-    selector_query: Query<&WeaponSelector, With<Player>>,
-    mut attack_events: EventWriter<AttackEvent>,
-    time: Res<Time>,
+// 更新時機條：即時戰鬥時隱藏，其他狀態顯示目前該做什麼、游標位置與出手結果
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_timing_bar_system(
+    state: Res<State<CombatState>>,
+    bindings: Res<InputBindings>,
+    player_query: Query<(Option<&TimingAttack>, Option<&TimingOpponent>, &CurrentWeapon), With<Player>>,
+    enemy_query: Query<(&Enemy, Option<&TimingAttack>)>,
+    mut bar_query: Query<&mut Visibility, With<TimingBar>>,
+    mut text_query: Query<&mut Text, With<TimingBarText>>,
+    mut cursor_query: Query<&mut Node, With<TimingBarCursor>>,
 ) {
-    // 武器選擇階段
-    if *current_state.get() == CombatState::PlayerSelecting {
-        if keyboard_input.just_pressed(KeyCode::Space) {
-            if let Ok(selector) = selector_query.single() {
-                let selected_weapon = selector.available_weapons[selector.selected_index];
-                
-                // 開始時機攻擊
-                if let Ok(player_entity) = timing_query.single() {
-                    let (entity, _, _, _, _) = player_entity;
-                    commands.entity(entity).insert(TimingAttack {
-                        stage: AttackStage::Warmup,
-                        warmup_timer: Timer::from_seconds(1.0, TimerMode::Once),
-                        action_timer: Timer::from_seconds(0.5, TimerMode::Once),
-                        cooldown_timer: Timer::from_seconds(0.5, TimerMode::Once),
-                        damage_multiplier: 1.0,
-                    });
-                    
-                    next_state.set(CombatState::PlayerAttacking);
-                    info!("開始時機攻擊 - 武器: {:?}", selected_weapon);
-                }
-            }
-        }
+    let Ok((player_timing, opponent, current_weapon)) = player_query.single() else {
+        return;
+    };
+    let opponent = opponent.and_then(|opponent| enemy_query.get(opponent.0).ok());
+
+    let visible = *state.get() != CombatState::Idle;
+    for mut visibility in &mut bar_query {
+        let target = if visible { Visibility::Inherited } else { Visibility::Hidden };
+        visibility.set_if_neq(target);
+    }
+    if !visible {
         return;
     }
 
-    // 攻擊執行階段
-    if *current_state.get() == CombatState::PlayerAttacking {
-        for (entity, mut timing_attack, transform, damage, current_weapon) in &mut timing_query {
-            match timing_attack.stage {
-                AttackStage::Warmup => {
-                    timing_attack.warmup_timer.tick(time.delta());
-                    if timing_attack.warmup_timer.finished() {
-                        timing_attack.stage = AttackStage::Action;
-                        info!("進入行動階段！按空白鍵攻擊！");
-                    }
-                },
-                AttackStage::Action => {
-                    timing_attack.action_timer.tick(time.delta());
-                    
-                    // 檢查時機輸入
-                    if keyboard_input.just_pressed(KeyCode::Space) {
-                        let elapsed_ratio = timing_attack.action_timer.elapsed_secs() / 
-                                          timing_attack.action_timer.duration().as_secs_f32();
-                        
-                        let timing = match elapsed_ratio {
-                            ratio if ratio < 0.3 => AttackTiming::Early,
-                            ratio if ratio > 0.7 => AttackTiming::Late,
-                            _ => AttackTiming::Critical,
-                        };
-                        
-                        // 根據時機調整傷害
-                        timing_attack.damage_multiplier = match timing {
-                            AttackTiming::Critical => 2.0, // 完美時機雙倍傷害
-                            AttackTiming::Early | AttackTiming::Late => 0.5, // 差時機減半傷害
-                        };
-                        
-                        // 發送攻擊事件
-                        attack_events.write(AttackEvent {
-                            position: transform.translation,
-                            direction: Vec2::Y, // 預設向上攻擊
-                            damage: (damage.0 as f32 * timing_attack.damage_multiplier) as i32,
-                            weapon_type: current_weapon.weapon_type,
-                            timing: Some(timing),
-                        });
-                        
-                        timing_attack.stage = AttackStage::CoolDown;
-                        info!("攻擊執行！時機: {:?}, 傷害倍數: {:.1}", timing, timing_attack.damage_multiplier);
-                    }
-                    
-                    // 如果時間到了還沒按，算作差時機
-                    if timing_attack.action_timer.finished() {
-                        attack_events.write(AttackEvent {
-                            position: transform.translation,
-                            direction: Vec2::Y,
-                            damage: (damage.0 as f32 * 0.5) as i32,
-                            weapon_type: current_weapon.weapon_type,
-                            timing: Some(AttackTiming::Late),
-                        });
-                        
-                        timing_attack.stage = AttackStage::CoolDown;
-                        info!("時間到！自動執行攻擊（Late timing）");
-                    }
-                },
-                AttackStage::CoolDown => {
-                    timing_attack.cooldown_timer.tick(time.delta());
-                    if timing_attack.cooldown_timer.finished() {
-                        // 移除時機攻擊組件並回到空閒狀態
-                        commands.entity(entity).remove::<TimingAttack>();
-                        commands.entity(entity).remove::<WeaponSelector>();
-                        next_state.set(CombatState::Idle);
-                        info!("攻擊完成，回到空閒狀態");
-                    }
-                },
-            }
+    let (message, progress) = match (state.get(), opponent) {
+        (CombatState::PlayerSelecting, Some((enemy, _))) => (
+            format!(
+                "Duel vs {:?} - {}\n{}: strike   {}: change weapon   {}: leave",
+                enemy.kind,
+                ItemKind::Weapon(current_weapon.weapon_type).label(),
+                bindings.describe(InputAction::Attack),
+                bindings.describe(InputAction::SwitchWeapon),
+                bindings.describe(InputAction::TimingAttack),
+            ),
+            0.0,
+        ),
+        (CombatState::PlayerAttacking, _) => match player_timing {
+            Some(timing_attack) => stage_message(timing_attack, "Get ready...", "Strike!", attack_result(timing_attack)),
+            None => (String::new(), 0.0),
+        },
+        (CombatState::EnemyAttacking, Some((enemy, Some(timing_attack)))) => stage_message(
+            timing_attack,
+            &format!("{:?} is winding up...", enemy.kind),
+            "Parry!",
+            parry_result(timing_attack),
+        ),
+        _ => (String::new(), 0.0),
+    };
+
+    for mut text in &mut text_query {
+        if text.0 != message {
+            text.0.clone_from(&message);
         }
     }
-}
\ No newline at end of file
+    for mut node in &mut cursor_query {
+        node.left = Val::Percent(progress * 100.0);
+    }
+}
+
+// 輔助函數：依出手的階段決定說明文字與游標位置
+fn stage_message(timing_attack: &TimingAttack, warmup: &str, action: &str, result: String) -> (String, f32) {
+    match timing_attack.stage {
+        AttackStage::Warmup => (warmup.to_string(), 0.0),
+        AttackStage::Action => (action.to_string(), timing_attack.action_progress()),
+        AttackStage::CoolDown => (result, timing_attack.action_progress()),
+    }
+}
+
+fn attack_result(timing_attack: &TimingAttack) -> String {
+    let label = match timing_attack.timing {
+        Some(AttackTiming::Critical) => "Critical!",
+        Some(AttackTiming::Early) => "Too early",
+        Some(AttackTiming::Late) => "Too late",
+        None => "Missed the window",
+    };
+    format!("{} x{:.1} damage", label, timing_attack.damage_multiplier)
+}
+
+fn parry_result(timing_attack: &TimingAttack) -> String {
+    match timing_attack.timing {
+        Some(AttackTiming::Critical) => "Perfect parry!".to_string(),
+        Some(AttackTiming::Early | AttackTiming::Late) => "Partly blocked".to_string(),
+        None => "Hit!".to_string(),
+    }
+}
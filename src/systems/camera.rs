@@ -1,29 +1,271 @@
 use bevy::prelude::*;
 use crate::components::player::Player;
-use crate::components::camera::CameraFollow;
+use crate::components::attack::PlayerFacing;
+use crate::components::camera::{CameraFollow, CameraPan, CameraEffects, CameraImpulseEvent};
+use crate::resources::{RoomGraph, CurrentRoom};
+use crate::systems::dungeon::RoomEnteredEvent;
+use crate::constants::*;
 
+/// 相機跟隨系統 - 跟隨玩家前方的目標點並限制在目前房間（走廊上時是走廊與兩端房間）內，小房間會放大到剛好填滿畫面
+#[allow(clippy::type_complexity)]
 pub fn camera_follow_system(
-    player_query: Query<&Transform, (With<Player>, Without<CameraFollow>)>,
-    mut camera_query: Query<(&mut Transform, &CameraFollow), (With<CameraFollow>, Without<Player>)>,
+    mut commands: Commands,
+    player_query: Query<(&Transform, &PlayerFacing), (With<Player>, Without<CameraFollow>)>,
+    mut camera_query: Query<
+        (Entity, &mut Transform, &mut Projection, &Camera, &CameraFollow, Option<&mut CameraPan>, Option<&CameraEffects>),
+        Without<Player>,
+    >,
+    graph: Option<Res<RoomGraph>>,
+    current_room: Res<CurrentRoom>,
     time: Res<Time>,
 ) {
-    if let (Ok(player_transform), Ok((mut camera_transform, camera_follow))) = 
-        (player_query.single(), camera_query.single_mut()) {
-        
-        let target_translation = player_transform.translation;
-        let current_translation = camera_transform.translation;
-        
-        // 使用線性插值進行平滑跟隨，速度可配置
-        let lerp_factor = camera_follow.speed * time.delta_secs();
-        let lerp_factor = lerp_factor.min(1.0); // 確保不會超過 1.0
-        
-        // 保持 Z 座標不變，只跟隨 X 和 Y
-        let new_translation = Vec3::new(
-            current_translation.x.lerp(target_translation.x, lerp_factor),
-            current_translation.y.lerp(target_translation.y, lerp_factor),
-            current_translation.z, // 保持相機的 Z 位置
-        );
-        
-        camera_transform.translation = new_translation;
-    }
-}
\ No newline at end of file
+    let (Ok((player_transform, facing)), Ok((entity, mut camera_transform, mut projection, camera, camera_follow, pan, effects))) =
+        (player_query.single(), camera_query.single_mut())
+    else {
+        return;
+    };
+
+    // 震動等效果的偏移不算在跟隨位置內
+    let effect_offset = effects.map_or(Vec2::ZERO, |effects| effects.offset);
+    let current = camera_transform.translation.truncate() - effect_offset;
+    let player_pos = player_transform.translation.truncate();
+    let look_target = player_pos + facing.direction * camera_follow.look_ahead;
+    let mut target = apply_dead_zone(current, look_target, camera_follow.dead_zone);
+
+    let bounds = graph.and_then(|graph| camera_bounds(&graph, current_room.0, player_pos));
+    if let (Some(bounds), Some(viewport), Projection::Orthographic(ortho)) =
+        (bounds, camera.logical_viewport_size(), projection.as_mut())
+    {
+        let zoom_factor = (CAMERA_ZOOM_SPEED * time.delta_secs()).min(1.0);
+        ortho.scale = ortho.scale.lerp(fit_scale(bounds.size(), viewport), zoom_factor);
+        target = clamp_to_bounds(target, viewport * ortho.scale / 2.0, bounds);
+    }
+
+    let new_position = match pan {
+        Some(mut pan) => {
+            pan.timer.tick(time.delta());
+            if pan.timer.finished() {
+                commands.entity(entity).remove::<CameraPan>();
+            }
+            let t = pan.timer.fraction();
+            pan.from.lerp(target, t * t * (3.0 - 2.0 * t))  // smoothstep
+        }
+        None => {
+            // 使用線性插值進行平滑跟隨，速度可配置
+            let lerp_factor = (camera_follow.speed * time.delta_secs()).min(1.0);
+            current.lerp(target, lerp_factor)
+        }
+    };
+
+    // 保持 Z 座標不變，只跟隨 X 和 Y
+    camera_transform.translation.x = new_position.x + effect_offset.x;
+    camera_transform.translation.y = new_position.y + effect_offset.y;
+}
+
+/// 相機效果系統 - 套用效果事件，更新震動與推動偏移，並在凍結時間結束後恢復遊戲時間
+///
+/// 使用真實時間計時，凍結期間相機仍會震動
+pub fn camera_effects_system(
+    mut impulse_events: EventReader<CameraImpulseEvent>,
+    mut camera_query: Query<(&mut Transform, &mut CameraEffects)>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Ok((mut transform, mut effects)) = camera_query.single_mut() else {
+        return;
+    };
+
+    for event in impulse_events.read() {
+        apply_impulse(&mut effects, event);
+    }
+
+    if effects.hit_stop > 0.0 {
+        virtual_time.pause();
+    }
+    let (offset, angle) = advance_effects(&mut effects, real_time.delta_secs());
+    if effects.hit_stop <= 0.0 && virtual_time.is_paused() {
+        virtual_time.unpause();
+    }
+
+    // 先移除上一幀的偏移再加上新的，跟隨系統看到的永遠是未偏移的位置
+    let delta = offset - effects.offset;
+    transform.translation.x += delta.x;
+    transform.translation.y += delta.y;
+    transform.rotation = Quat::from_rotation_z(angle);
+    effects.offset = offset;
+}
+
+fn apply_impulse(effects: &mut CameraEffects, event: &CameraImpulseEvent) {
+    match *event {
+        CameraImpulseEvent::Shake { trauma } => effects.trauma = (effects.trauma + trauma).clamp(0.0, 1.0),
+        CameraImpulseEvent::HitStop { duration } => effects.hit_stop = effects.hit_stop.max(duration),
+        CameraImpulseEvent::Kick { direction, strength } => effects.kick += direction.normalize_or_zero() * strength,
+    }
+}
+
+/// 推進效果時間並回傳這一幀的位移與旋轉
+fn advance_effects(effects: &mut CameraEffects, delta_secs: f32) -> (Vec2, f32) {
+    effects.hit_stop = (effects.hit_stop - delta_secs).max(0.0);
+    effects.trauma = (effects.trauma - CAMERA_TRAUMA_DECAY * delta_secs).max(0.0);
+    effects.kick *= (-CAMERA_KICK_DECAY * delta_secs).exp();
+    if effects.kick.length() < 0.1 {
+        effects.kick = Vec2::ZERO;
+    }
+
+    if effects.trauma == 0.0 {
+        effects.elapsed = 0.0;
+        return (effects.kick, 0.0);
+    }
+
+    effects.elapsed += delta_secs;
+    let shake = effects.trauma * effects.trauma;
+    let t = effects.elapsed * CAMERA_SHAKE_FREQUENCY;
+    let shake_offset = Vec2::new(shake_noise(t, 0.0), shake_noise(t, 17.0)) * CAMERA_SHAKE_MAX_OFFSET * shake;
+    (effects.kick + shake_offset, shake_noise(t, 41.0) * CAMERA_SHAKE_MAX_ANGLE * shake)
+}
+
+/// 平滑的偽隨機雜訊（-1 到 1），不使用 GameRng，震動不會影響同一個種子的遊戲結果
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    ((t + seed).sin() + (t * 1.7 + seed * 2.3).sin() * 0.5) / 1.5
+}
+
+/// 進入新房間時從目前位置平移到新房間
+pub fn start_camera_pan_system(
+    mut commands: Commands,
+    mut entered_events: EventReader<RoomEnteredEvent>,
+    camera_query: Query<(Entity, &Transform), With<CameraFollow>>,
+) {
+    if entered_events.read().count() == 0 {
+        return;
+    }
+    if let Ok((entity, transform)) = camera_query.single() {
+        commands.entity(entity).insert(CameraPan {
+            from: transform.translation.truncate(),
+            timer: Timer::from_seconds(CAMERA_PAN_TIME, TimerMode::Once),
+        });
+    }
+}
+
+/// 相機的限制範圍：在房間地板上時是目前房間；在走廊或門上時是走廊與兩端的房間
+///
+/// CurrentRoom 在走廊上不會更新，只用房間範圍的話，走向上鎖的門（不會切換房間）時玩家會走出畫面
+fn camera_bounds(graph: &RoomGraph, current_room: Option<usize>, position: Vec2) -> Option<Rect> {
+    if graph.room_at(position).is_some() {
+        return current_room.map(|room| graph.room_bounds(room));
+    }
+    graph.corridor_at(position).map(|edge| graph.corridor_bounds(edge))
+}
+
+/// 目標離相機中心超過死區才移動，且只移動超出的部分
+fn apply_dead_zone(current: Vec2, target: Vec2, dead_zone: Vec2) -> Vec2 {
+    let offset = target - current;
+    current + offset.signum() * (offset.abs() - dead_zone).max(Vec2::ZERO)
+}
+
+/// 讓房間剛好填滿畫面的縮放比例；大房間維持原本大小，小房間最多放大到 CAMERA_MIN_SCALE
+fn fit_scale(room_size: Vec2, viewport: Vec2) -> f32 {
+    let ratio = room_size / viewport;
+    ratio.max_element().clamp(CAMERA_MIN_SCALE, 1.0)
+}
+
+/// 把相機中心限制在畫面不超出房間的範圍；房間比畫面小的方向置中
+fn clamp_to_bounds(center: Vec2, half_view: Vec2, bounds: Rect) -> Vec2 {
+    let clamp_axis = |value: f32, half: f32, min: f32, max: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) / 2.0
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+    Vec2::new(
+        clamp_axis(center.x, half_view.x, bounds.min.x, bounds.max.x),
+        clamp_axis(center.y, half_view.y, bounds.min.y, bounds.max.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trauma_accumulates_clamps_and_decays() {
+        let mut effects = CameraEffects::default();
+        apply_impulse(&mut effects, &CameraImpulseEvent::Shake { trauma: 0.7 });
+        apply_impulse(&mut effects, &CameraImpulseEvent::Shake { trauma: 0.7 });
+        assert_eq!(effects.trauma, 1.0);
+
+        let (offset, _) = advance_effects(&mut effects, 0.1);
+        assert!(offset.length() <= CAMERA_SHAKE_MAX_OFFSET * 2.0_f32.sqrt());
+        assert!(effects.trauma < 1.0);
+
+        for _ in 0..100 {
+            advance_effects(&mut effects, 0.1);
+        }
+        assert_eq!(advance_effects(&mut effects, 0.1), (Vec2::ZERO, 0.0));
+    }
+
+    #[test]
+    fn kick_springs_back_and_hit_stop_keeps_longest() {
+        let mut effects = CameraEffects::default();
+        apply_impulse(&mut effects, &CameraImpulseEvent::Kick { direction: Vec2::new(3.0, 0.0), strength: 10.0 });
+        apply_impulse(&mut effects, &CameraImpulseEvent::HitStop { duration: 0.05 });
+        apply_impulse(&mut effects, &CameraImpulseEvent::HitStop { duration: 0.02 });
+        assert_eq!(effects.kick, Vec2::new(10.0, 0.0));
+        assert_eq!(effects.hit_stop, 0.05);
+
+        let (offset, _) = advance_effects(&mut effects, 0.05);
+        assert!(offset.x > 0.0 && offset.x < 10.0);
+        assert_eq!(effects.hit_stop, 0.0);
+        for _ in 0..20 {
+            advance_effects(&mut effects, 0.05);
+        }
+        assert_eq!(effects.kick, Vec2::ZERO);
+    }
+
+    #[test]
+    fn corridor_bounds_cover_both_rooms_and_the_corridor() {
+        use crate::systems::dungeon::generate_floor_layout;
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let graph = generate_floor_layout(&mut StdRng::seed_from_u64(7), &[]);
+        let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+        let (index, edge) = graph.edges.iter().enumerate()
+            .find(|(_, edge)| !edge.corridor.is_empty())
+            .unwrap();
+
+        // 走廊上：不論 CurrentRoom 是哪一端，範圍都包含走廊與兩端房間
+        let corridor = edge.corridor[edge.corridor.len() / 2].as_vec2() * tile_size;
+        let bounds = camera_bounds(&graph, Some(edge.a), corridor).unwrap();
+        assert_eq!(graph.corridor_at(corridor), Some(index));
+        assert!(bounds.contains(corridor));
+        assert_eq!(bounds.union(graph.room_bounds(edge.a)).union(graph.room_bounds(edge.b)), bounds);
+
+        // 房間裡：只限制在目前房間
+        let center = graph.room_center(edge.a);
+        assert_eq!(camera_bounds(&graph, Some(edge.a), center), Some(graph.room_bounds(edge.a)));
+    }
+
+    #[test]
+    fn dead_zone_only_moves_by_the_overshoot() {
+        let dead_zone = Vec2::new(30.0, 20.0);
+        assert_eq!(apply_dead_zone(Vec2::ZERO, Vec2::new(10.0, -15.0), dead_zone), Vec2::ZERO);
+        assert_eq!(apply_dead_zone(Vec2::ZERO, Vec2::new(50.0, -25.0), dead_zone), Vec2::new(20.0, -5.0));
+    }
+
+    #[test]
+    fn small_rooms_zoom_in_and_large_rooms_keep_scale() {
+        let viewport = Vec2::new(1280.0, 720.0);
+        assert_eq!(fit_scale(Vec2::new(960.0, 480.0), viewport), 0.75);
+        assert_eq!(fit_scale(Vec2::new(200.0, 100.0), viewport), CAMERA_MIN_SCALE);
+        assert_eq!(fit_scale(Vec2::new(2000.0, 400.0), viewport), 1.0);
+    }
+
+    #[test]
+    fn camera_stays_inside_room_and_centers_small_axes() {
+        let bounds = Rect::new(0.0, 0.0, 1000.0, 300.0);
+        let half_view = Vec2::new(200.0, 200.0);
+        assert_eq!(clamp_to_bounds(Vec2::new(-50.0, 100.0), half_view, bounds), Vec2::new(200.0, 150.0));
+        assert_eq!(clamp_to_bounds(Vec2::new(900.0, 0.0), half_view, bounds), Vec2::new(800.0, 150.0));
+        assert_eq!(clamp_to_bounds(Vec2::new(500.0, 0.0), half_view, bounds), Vec2::new(500.0, 150.0));
+    }
+}
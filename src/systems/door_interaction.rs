@@ -1,134 +1,205 @@
 use bevy::prelude::*;
 use crate::components::player::Player;
+use crate::components::inventory::{Inventory, ItemKind, InventoryEvent};
 use crate::components::world::{Door, RoomTile, RoomTileType};
-use crate::systems::input::DoorInteractionEvent;
-use crate::resources::RoomAssets;
+use crate::components::interaction::{Interactable, InteractEvent};
+use crate::components::camera::CameraImpulseEvent;
+use crate::resources::{RoomAssets, RoomGraph, CurrentRoom, ClearedRooms};
+use crate::systems::dungeon::RoomClearedEvent;
 use crate::constants::*;
 
-/// 門交互系統 - 處理玩家與門的碰撞檢測和開關邏輯
+/// 門設定系統 - 樓層生成後依房間圖標記每扇門所屬的房間、相連的門與是否上鎖
+pub fn assign_doors_system(mut door_query: Query<&mut Door>, graph: Res<RoomGraph>) {
+    let mut locked = 0;
+    for mut door in &mut door_query {
+        for edge in &graph.edges {
+            if edge.door_a == door.cell {
+                // 上鎖的連線只鎖 a 側的門，一把鑰匙就能打通
+                door.room = Some(edge.a);
+                door.key_locked = edge.locked;
+                door.link = Some(edge.door_b);
+                door.outward = edge.corridor.first().map_or(edge.door_b - edge.door_a, |cell| *cell - edge.door_a);
+            } else if edge.door_b == door.cell {
+                door.room = Some(edge.b);
+                door.link = Some(edge.door_a);
+                door.outward = edge.corridor.last().map_or(edge.door_a - edge.door_b, |cell| *cell - edge.door_b);
+            }
+        }
+        if door.key_locked {
+            locked += 1;
+        }
+    }
+    info!("樓層上有 {} 扇上鎖的門", locked);
+}
+
+/// 門交互系統 - 玩家對門按下互動鍵時切換開關；上鎖的門消耗一把鑰匙打開，封鎖的門打不開
 pub fn door_interaction_system(
-    mut door_query: Query<(Entity, &mut Door, &mut RoomTile, &Transform, &mut Sprite), Without<Player>>,
-    player_query: Query<&Transform, With<Player>>,
-    mut door_events: EventReader<DoorInteractionEvent>,
+    mut door_query: Query<(&mut Door, &mut RoomTile, &mut Sprite, &Transform)>,
+    mut player_query: Query<(&Transform, &mut Inventory), With<Player>>,
+    mut interact_events: EventReader<InteractEvent>,
+    mut inventory_events: EventWriter<InventoryEvent>,
     room_assets: Res<RoomAssets>,
 ) {
-    let player_transform = match player_query.single() {
-        Ok(transform) => transform,
-        Err(_) => return, // 沒有玩家就退出
+    let Ok((player_transform, mut inventory)) = player_query.single_mut() else {
+        return;
     };
-    
-    // 調試：檢查是否有門存在
-    static mut DEBUG_COUNTER: u32 = 0;
-    unsafe {
-        DEBUG_COUNTER += 1;
-        if DEBUG_COUNTER % 300 == 0 { // 每10秒檢查一次
-            let door_count = door_query.iter().count();
-            info!("門交互系統運行中，找到 {} 個門", door_count);
-            for (entity, door, room_tile, door_transform, sprite) in door_query.iter() {
-                info!("  門位置: {:?}, 開啟狀態: {}", door_transform.translation.truncate(), door.is_open);
-            }
+
+    for event in interact_events.read() {
+        let Ok((mut door, mut room_tile, mut sprite, door_transform)) = door_query.get_mut(event.target) else {
+            continue; // 不是門
+        };
+
+        if door.sealed {
+            info!("🚪 門被封鎖了，先擊倒房間裡的敵人");
+            continue;
         }
-    }
 
-    // 只有在收到門交互事件時才處理
-    for _event in door_events.read() {
-        let mut closest_door = None;
-        let mut closest_distance = f32::INFINITY;
-        
-        // 找到最近的門
-        for (entity, door, room_tile, door_transform, sprite) in door_query.iter() {
-            let distance = player_transform.translation.distance(door_transform.translation);
-            let interaction_distance = ROOM_TILE_SIZE * PLAYER_SCALE * 10.0; // 10個瓷磚的距離，約480像素
-            
-            if distance <= interaction_distance && distance < closest_distance {
-                closest_distance = distance;
-                closest_door = Some((entity, door, room_tile, door_transform, sprite));
+        if door.key_locked {
+            if !inventory.remove(ItemKind::Key, 1) {
+                info!("🔒 這扇門需要鑰匙");
+                continue;
             }
+            door.key_locked = false;
+            inventory_events.write(InventoryEvent::Removed { item: ItemKind::Key, count: 1 });
+            info!("🔑 用掉一把鑰匙打開了門（剩下 {} 把）", inventory.count(ItemKind::Key));
         }
-        
-        // 如果找到最近的門，切換其狀態
-        if let Some((entity, _door, _room_tile, _door_transform, _sprite)) = closest_door {
-            // 通過entity重新獲取可變引用
-            if let Ok((_, mut door, mut room_tile, _, mut sprite)) = door_query.get_mut(entity) {
-                // 切換門的狀態
-                door.is_open = !door.is_open;
-                
-                // 更新視覺效果
-                if door.is_open {
-                    room_tile.tile_type = RoomTileType::DoorOpen;
-                    sprite.image = room_assets.door_open.clone();
-                    info!("🚪 門已開啟！玩家現在可以通過");
-                } else {
-                    room_tile.tile_type = RoomTileType::DoorClosed;
-                    sprite.image = room_assets.door_closed.clone();
-                    info!("🚪 門已關閉！玩家無法通過");
-                }
-            }
+
+        let open = !door.is_open;
+        if !open && player_in_doorway(player_transform, door_transform) {
+            continue; // 站在門口時不能關門
+        }
+        set_door_open(&mut door, &mut room_tile, &mut sprite, &room_assets, open);
+
+        if open {
+            info!("🚪 門已開啟！玩家現在可以通過");
+        } else {
+            info!("🚪 門已關閉！玩家無法通過");
         }
     }
 }
 
-/// 門碰撞檢測系統 - 阻止玩家穿過關閉的門
-pub fn door_collision_system(
-    door_query: Query<(&Door, &Transform), Without<Player>>,
-    mut player_query: Query<&mut Transform, With<Player>>,
+/// 門的互動提示跟著開關與上鎖狀態更新
+pub fn sync_door_prompt_system(mut door_query: Query<(&Door, &mut Interactable), Changed<Door>>) {
+    for (door, mut interactable) in &mut door_query {
+        interactable.prompt = door_prompt(door).to_string();
+    }
+}
+
+pub fn door_prompt(door: &Door) -> &'static str {
+    if door.sealed {
+        "Sealed"
+    } else if door.key_locked {
+        "Unlock (key)"
+    } else if door.is_open {
+        "Close"
+    } else {
+        "Open"
+    }
+}
+
+/// 房間封鎖系統 - 玩家進入還有敵人的房間後關上並封鎖房間的門，房間清除後解除封鎖
+pub fn seal_room_doors_system(
+    mut door_query: Query<(&mut Door, &mut RoomTile, &mut Sprite, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+    current_room: Res<CurrentRoom>,
+    cleared_rooms: Res<ClearedRooms>,
+    room_assets: Res<RoomAssets>,
+    mut impulse_events: EventWriter<CameraImpulseEvent>,
 ) {
-    let mut player_transform = match player_query.single_mut() {
-        Ok(transform) => transform,
-        Err(_) => return,
+    let Ok(player_transform) = player_query.single() else {
+        return;
     };
-    
-    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let collision_threshold = tile_size * 0.4; // 40% 的瓷磚尺寸作為碰撞檢測閾值
-    
-    for (door, door_transform) in &door_query {
-        // 只檢查關閉的門
-        if !door.is_open {
-            let door_pos = door_transform.translation.truncate();
-            let player_pos = player_transform.translation.truncate();
-            let distance = door_pos.distance(player_pos);
-            
-            // 如果玩家太靠近關閉的門
-            if distance < collision_threshold {
-                // 計算推開玩家的方向
-                let push_direction = (player_pos - door_pos).normalize_or_zero();
-                
-                // 將玩家推到安全距離
-                let safe_distance = collision_threshold + 2.0;
-                let new_position = door_pos + push_direction * safe_distance;
-                
-                player_transform.translation.x = new_position.x;
-                player_transform.translation.y = new_position.y;
+
+    let mut slammed = false;
+    for (mut door, mut room_tile, mut sprite, door_transform) in &mut door_query {
+        let Some(room) = door.room else {
+            continue;
+        };
+        let cleared = cleared_rooms.0.contains(&room);
+
+        if door.sealed && cleared {
+            door.sealed = false;
+            info!("🚪 房間 {} 已清除，門的封鎖解除", room);
+        } else if !door.sealed
+            && !cleared
+            && current_room.0 == Some(room)
+            // 等玩家離開門口一段距離才封鎖，避免把玩家關在門裡
+            && player_transform.translation.distance(door_transform.translation) > DOOR_SEAL_CLEARANCE
+        {
+            door.sealed = true;
+            if door.is_open {
+                set_door_open(&mut door, &mut room_tile, &mut sprite, &room_assets, false);
+                slammed = true;
             }
         }
     }
+
+    // 門被用力關上
+    if slammed {
+        impulse_events.write(CameraImpulseEvent::Shake { trauma: DOOR_SLAM_TRAUMA });
+    }
 }
 
-/// 自動門關閉系統 - 玩家離開後自動關閉門
+/// 清除有敵人的房間時獲得一把鑰匙
+pub fn grant_key_system(
+    mut cleared_events: EventReader<RoomClearedEvent>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    mut inventory_events: EventWriter<InventoryEvent>,
+) {
+    let Ok(mut inventory) = player_query.single_mut() else {
+        return;
+    };
+
+    for event in cleared_events.read() {
+        if inventory.add(ItemKind::Key, 1) == 0 {
+            info!("🔑 清除房間 {}，但鑰匙已經帶滿了", event.room);
+            continue;
+        }
+        inventory_events.write(InventoryEvent::Added { item: ItemKind::Key, count: 1 });
+        info!("🔑 清除房間 {} 獲得一把鑰匙（共 {} 把）", event.room, inventory.count(ItemKind::Key));
+    }
+}
+
+/// 自動門關閉系統 - 玩家離開開啟的門一段距離後自動關上
 pub fn auto_close_door_system(
-    mut door_query: Query<(Entity, &mut Door, &mut RoomTile, &Transform, &mut Sprite)>,
+    mut door_query: Query<(&mut Door, &mut RoomTile, &Transform, &mut Sprite)>,
     player_query: Query<&Transform, With<Player>>,
     room_assets: Res<RoomAssets>,
-    _time: Res<Time>,
 ) {
     let player_transform = match player_query.single() {
         Ok(transform) => transform,
         Err(_) => return,
     };
 
-    for (_entity, mut door, mut room_tile, door_transform, mut sprite) in &mut door_query {
+    for (mut door, mut room_tile, door_transform, mut sprite) in &mut door_query {
         // 只處理開啟的門
         if door.is_open {
             let distance = player_transform.translation.distance(door_transform.translation);
-            let auto_close_distance = ROOM_TILE_SIZE * PLAYER_SCALE * 15.0; // 15個瓷磚距離自動關閉
-            
+
             // 如果玩家離得夠遠，自動關閉門
-            if distance > auto_close_distance {
-                door.is_open = false;
-                room_tile.tile_type = RoomTileType::DoorClosed;
-                sprite.image = room_assets.door_closed.clone();
+            if distance > DOOR_AUTO_CLOSE_DISTANCE {
+                set_door_open(&mut door, &mut room_tile, &mut sprite, &room_assets, false);
                 info!("門自動關閉！");
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 玩家的碰撞箱是否和門的格子重疊
+pub fn player_in_doorway(player_transform: &Transform, door_transform: &Transform) -> bool {
+    let reach = ROOM_TILE_SIZE * PLAYER_SCALE / 2.0 + PLAYER_COLLIDER_HALF_SIZE;
+    let offset = (player_transform.translation - door_transform.translation).truncate().abs();
+    offset.x < reach && offset.y < reach
+}
+
+/// 設定門的開關狀態，同步更新瓷磚類型與圖片
+pub fn set_door_open(door: &mut Door, room_tile: &mut RoomTile, sprite: &mut Sprite, room_assets: &RoomAssets, open: bool) {
+    door.is_open = open;
+    if open {
+        room_tile.tile_type = RoomTileType::DoorOpen;
+        sprite.image = room_assets.door_open.clone();
+    } else {
+        room_tile.tile_type = RoomTileType::DoorClosed;
+        sprite.image = room_assets.door_closed.clone();
+    }
+}
@@ -0,0 +1,621 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+use crate::components::player::Player;
+use crate::components::enemy::Enemy;
+use bevy::asset::{LoadState, LoadedFolder};
+use crate::components::world::{CompoundRoom, DoorSocket, FloorRegion, RoomRect, RoomTileType, TileGrid, WallSide};
+use crate::resources::{
+    RoomAssets, RoomGraph, RoomNode, RoomEdge, CurrentRoom, ClearedRooms, DungeonSeed, RngStream,
+    RoomTemplate, RoomTemplateFolder,
+};
+use crate::systems::world::{generate_room_template, build_room_grid, spawn_tile_grid};
+use crate::states::InRun;
+use crate::constants::*;
+
+const SLOT_DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+
+/// 產生整層樓的房間圖（純資料，不生成實體）
+///
+/// 1. 在佈局格上隨機擴張出 8–20 個房間位置
+/// 2. 每個位置放入程序生成的房間，或依機率改用手工樣板
+/// 3. 以相鄰位置為候選連線，用 Kruskal 取生成樹，再隨機加入額外連線形成迴圈
+/// 4. 在相連房間相對的牆上開門（優先使用樣板的門插槽），並在兩扇門之間挖出走廊
+pub fn generate_floor_layout(rng: &mut impl Rng, templates: &[RoomTemplate]) -> RoomGraph {
+    let slots = generate_room_slots(rng);
+    let slot_index: HashMap<IVec2, usize> = slots.iter().enumerate().map(|(i, slot)| (*slot, i)).collect();
+
+    // 第一個位置是起始房間
+    let rooms: Vec<RoomNode> = slots.iter().enumerate()
+        .map(|(index, slot)| generate_room_node(rng, templates, *slot, index == 0))
+        .collect();
+
+    // 所有相鄰位置都是候選連線（a 在 b 的西邊或南邊）
+    let mut candidates: Vec<(usize, usize)> = slots.iter().enumerate()
+        .flat_map(|(a, slot)| {
+            [IVec2::X, IVec2::Y].into_iter()
+                .filter_map(|step| slot_index.get(&(*slot + step)).map(|b| (a, *b)))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    candidates.shuffle(rng);
+
+    // Kruskal 生成樹 + 額外迴圈
+    let mut parent: Vec<usize> = (0..rooms.len()).collect();
+    let mut connections = Vec::new();
+    for (a, b) in candidates {
+        let root_a = find_root(&mut parent, a);
+        let root_b = find_root(&mut parent, b);
+        if root_a != root_b {
+            parent[root_a] = root_b;
+            connections.push((a, b, false));
+        } else if rng.gen_bool(FLOOR_EXTRA_LOOP_CHANCE) {
+            connections.push((a, b, true));
+        }
+    }
+
+    // 只有額外迴圈（捷徑）可能上鎖，不用鑰匙也能走到每個房間
+    let edges = connections.into_iter()
+        .map(|(a, b, is_loop)| {
+            let mut edge = connect_rooms(rng, &rooms, a, b);
+            edge.locked = is_loop && rng.gen_bool(FLOOR_LOCKED_DOOR_CHANCE);
+            edge
+        })
+        .collect();
+
+    RoomGraph {
+        rooms,
+        edges,
+        start_room: 0,
+    }
+}
+
+/// 決定某個位置的房間：起始房間優先使用標記 start 的樣板，其他房間依機率混入其餘樣板
+fn generate_room_node(rng: &mut impl Rng, templates: &[RoomTemplate], slot: IVec2, is_start: bool) -> RoomNode {
+    let offset = slot * FLOOR_SLOT_SIZE;
+    let candidates: Vec<&RoomTemplate> = templates.iter()
+        .filter(|template| template.has_tag(ROOM_TAG_START) == is_start)
+        .collect();
+
+    let use_template = !candidates.is_empty() && (is_start || rng.gen_bool(FLOOR_CUSTOM_ROOM_CHANCE));
+    let template = if use_template {
+        candidates.choose_weighted(rng, |template| template.weight).ok()
+    } else {
+        None
+    };
+
+    match template {
+        Some(template) => RoomNode {
+            slot,
+            layout: template.layout().translated(offset),
+            template: Some(template.name.clone()),
+            door_sockets: template.door_sockets.iter()
+                .map(|socket| DoorSocket { cell: socket.cell + offset, side: socket.side })
+                .collect(),
+            spawn_points: template.spawn_points.iter().map(|point| *point + offset).collect(),
+        },
+        None => RoomNode {
+            slot,
+            layout: generate_room_template(rng).translated(offset),
+            template: None,
+            door_sockets: Vec::new(),
+            spawn_points: Vec::new(),
+        },
+    }
+}
+
+/// 從原點開始隨機擴張，保證所有位置彼此相鄰連通
+fn generate_room_slots(rng: &mut impl Rng) -> Vec<IVec2> {
+    let room_count = rng.gen_range(FLOOR_MIN_ROOMS..=FLOOR_MAX_ROOMS);
+    let mut slots = vec![IVec2::ZERO];
+    let mut occupied: HashSet<IVec2> = slots.iter().copied().collect();
+
+    while slots.len() < room_count {
+        let from = slots[rng.gen_range(0..slots.len())];
+        let next = from + SLOT_DIRECTIONS[rng.gen_range(0..SLOT_DIRECTIONS.len())];
+        if occupied.insert(next) {
+            slots.push(next);
+        }
+    }
+
+    slots
+}
+
+fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+/// 連接相鄰的兩個房間：b 必定在 a 的東邊或北邊
+///
+/// 有對應方向的門插槽就隨機選一個，否則在最外側矩形的牆中央開門；
+/// 走廊的轉折固定在兩個房間之間的空隙，不會穿過任何一個房間
+fn connect_rooms(rng: &mut impl Rng, rooms: &[RoomNode], a: usize, b: usize) -> RoomEdge {
+    let room_a = &rooms[a].layout;
+    let room_b = &rooms[b].layout;
+    let (_, max_a) = room_a.footprint_bounds();
+    let (min_b, _) = room_b.footprint_bounds();
+    let horizontal = rooms[b].slot.x > rooms[a].slot.x;
+
+    let (door_a, door_b, corridor) = if horizontal {
+        // a 東牆 -> b 西牆
+        let door_a = pick_socket(rng, &rooms[a], WallSide::East).unwrap_or_else(|| {
+            let east = extreme_rect(room_a, |rect| rect.x + rect.width as i32 - 1);
+            IVec2::new(east.x + east.width as i32 - 1, mid_floor(east).y)
+        });
+        let door_b = pick_socket(rng, &rooms[b], WallSide::West).unwrap_or_else(|| {
+            let west = extreme_rect(room_b, |rect| -rect.x);
+            IVec2::new(west.x, mid_floor(west).y)
+        });
+
+        let start = door_a + IVec2::X;
+        let end = door_b - IVec2::X;
+        let mid_x = (max_a.x + min_b.x) / 2;
+        let corridor = carve_path(&[start, IVec2::new(mid_x, start.y), IVec2::new(mid_x, end.y), end]);
+        (door_a, door_b, corridor)
+    } else {
+        // a 北牆 -> b 南牆（南牆外側那一排）
+        let door_a = pick_socket(rng, &rooms[a], WallSide::North).unwrap_or_else(|| {
+            let north = extreme_rect(room_a, |rect| rect.y + rect.height as i32 - 1);
+            IVec2::new(mid_floor(north).x, north.y + north.height as i32 - 1)
+        });
+        let door_b = pick_socket(rng, &rooms[b], WallSide::South).unwrap_or_else(|| {
+            let south = extreme_rect(room_b, |rect| -rect.y);
+            IVec2::new(mid_floor(south).x, south.y - 1)
+        });
+
+        let start = door_a + IVec2::Y;
+        let end = door_b - IVec2::Y;
+        let mid_y = (max_a.y + min_b.y) / 2;
+        let corridor = carve_path(&[start, IVec2::new(start.x, mid_y), IVec2::new(end.x, mid_y), end]);
+        (door_a, door_b, corridor)
+    };
+
+    RoomEdge { a, b, door_a, door_b, corridor, locked: false }
+}
+
+/// 從房間指定方向的門插槽中隨機選一個，回傳門的格子
+fn pick_socket(rng: &mut impl Rng, room: &RoomNode, side: WallSide) -> Option<IVec2> {
+    let sockets: Vec<_> = room.door_sockets.iter().filter(|socket| socket.side == side).collect();
+    sockets.choose(rng).map(|socket| socket.door_cell())
+}
+
+/// 取出某方向最外側的矩形
+fn extreme_rect(room: &CompoundRoom, key: impl Fn(&RoomRect) -> i32) -> &RoomRect {
+    room.rectangles.iter()
+        .max_by_key(|rect| key(rect))
+        .expect("房間至少要有一個矩形")
+}
+
+fn mid_floor(rect: &RoomRect) -> IVec2 {
+    let (min, max) = rect.floor_cells();
+    (min + max) / 2
+}
+
+/// 依序連接轉折點，回傳沿途經過的所有格子
+fn carve_path(points: &[IVec2]) -> Vec<IVec2> {
+    let mut cells = vec![points[0]];
+    for pair in points.windows(2) {
+        let mut current = pair[0];
+        let step = (pair[1] - pair[0]).signum();
+        while current != pair[1] {
+            current += step;
+            cells.push(current);
+        }
+    }
+    cells.dedup();
+    cells
+}
+
+/// 把整層樓（房間、門、走廊與走廊牆壁）合併成一張瓷磚格
+pub fn build_floor_grid(graph: &RoomGraph) -> TileGrid {
+    let mut grid = TileGrid::default();
+
+    for (index, node) in graph.rooms.iter().enumerate() {
+        let doors: Vec<IVec2> = graph.doors(index).collect();
+        build_room_grid(&mut grid, &node.layout, &doors);
+    }
+
+    let corridor_cells: Vec<IVec2> = graph.edges.iter()
+        .flat_map(|edge| edge.corridor.iter().copied())
+        .collect();
+    for cell in &corridor_cells {
+        grid.place(*cell, RoomTileType::Floor);
+    }
+
+    // 走廊四周還沒有瓷磚的格子補上牆壁，依相對位置選擇牆面
+    for cell in &corridor_cells {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let neighbor = *cell + IVec2::new(dx, dy);
+                if grid.get(neighbor).is_some() {
+                    continue;
+                }
+                let tile_type = match (dx, dy) {
+                    (_, 1) => RoomTileType::WallNInnerMid,
+                    (_, -1) => RoomTileType::WallSOuterMid,
+                    (-1, _) => RoomTileType::WallWSide,
+                    _ => RoomTileType::WallESide,
+                };
+                grid.place(neighbor, tile_type);
+            }
+        }
+    }
+
+    grid
+}
+
+/// 房間佔用的所有格子（含牆壁與南牆外側）
+fn room_footprint(room: &CompoundRoom) -> HashSet<IVec2> {
+    room.rectangles.iter()
+        .flat_map(|rect| {
+            (rect.y - 1..rect.y + rect.height as i32).flat_map(move |y| {
+                (rect.x..rect.x + rect.width as i32).map(move |x| IVec2::new(x, y))
+            })
+        })
+        .collect()
+}
+
+/// 標記瓷磚格上每一格屬於哪個房間或哪條連線（門、走廊與走廊牆壁）
+pub fn floor_regions(graph: &RoomGraph, grid: &TileGrid) -> HashMap<IVec2, FloorRegion> {
+    let mut regions = HashMap::new();
+
+    for (index, node) in graph.rooms.iter().enumerate() {
+        for cell in room_footprint(&node.layout) {
+            if grid.get(cell).is_some() {
+                regions.insert(cell, FloorRegion::Room(index));
+            }
+        }
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        regions.insert(edge.door_a, FloorRegion::Corridor(index));
+        regions.insert(edge.door_b, FloorRegion::Corridor(index));
+        for cell in &edge.corridor {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let neighbor = *cell + IVec2::new(dx, dy);
+                    if grid.get(neighbor).is_some() {
+                        regions.entry(neighbor).or_insert(FloorRegion::Corridor(index));
+                    }
+                }
+            }
+        }
+    }
+
+    regions
+}
+
+/// 樓層尚未生成，且樣板資料夾與其中每個檔案都已載入完成（或失敗）
+///
+/// 等所有樣板都有結果才生成，同一個種子才會得到相同的樓層
+pub fn floor_ready_to_generate(
+    graph: Option<Res<RoomGraph>>,
+    folder: Option<Res<RoomTemplateFolder>>,
+    folders: Res<Assets<LoadedFolder>>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    let (None, Some(folder)) = (graph, folder) else {
+        return false;
+    };
+
+    match asset_server.load_state(&folder.0) {
+        LoadState::Failed(_) => true,
+        LoadState::Loaded => folders.get(&folder.0).is_some_and(|loaded| {
+            loaded.handles.iter().all(|handle| {
+                matches!(asset_server.load_state(handle.id()), LoadState::Loaded | LoadState::Failed(_))
+            })
+        }),
+        _ => false,
+    }
+}
+
+/// 取出所有成功載入的樣板，依名稱排序讓生成結果不受檔案系統順序影響
+fn loaded_room_templates(
+    folder: &RoomTemplateFolder,
+    folders: &Assets<LoadedFolder>,
+    templates: &Assets<RoomTemplate>,
+) -> Vec<RoomTemplate> {
+    let Some(loaded) = folders.get(&folder.0) else {
+        warn!("無法載入房間樣板資料夾 assets/{}，只使用程序生成的房間", ROOM_TEMPLATE_DIR);
+        return Vec::new();
+    };
+
+    let mut result: Vec<RoomTemplate> = loaded.handles.iter()
+        .filter_map(|handle| handle.clone().try_typed::<RoomTemplate>().ok())
+        .filter_map(|handle| templates.get(&handle).cloned())
+        .collect();
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    result
+}
+
+/// 樓層生成系統 - 產生房間圖並生成所有房間、門與走廊
+pub fn spawn_dungeon_floor(
+    mut commands: Commands,
+    room_assets: Res<RoomAssets>,
+    seed: Res<DungeonSeed>,
+    folder: Res<RoomTemplateFolder>,
+    folders: Res<Assets<LoadedFolder>>,
+    room_templates: Res<Assets<RoomTemplate>>,
+) {
+    let templates = loaded_room_templates(&folder, &folders, &room_templates);
+    info!("已載入 {} 個手工房間樣板", templates.len());
+
+    info!("🎲 地城種子: {}（使用 --seed {} 或 DUNGEON_SEED={} 重現此樓層）", seed.0, seed.0, seed.0);
+    let mut rng = seed.rng(RngStream::FloorLayout);
+    let graph = generate_floor_layout(&mut rng, &templates);
+
+    let grid = build_floor_grid(&graph);
+    spawn_tile_grid(&mut commands, &room_assets, &grid, &floor_regions(&graph, &grid));
+
+    // 創建複合房間實體
+    for node in &graph.rooms {
+        commands.spawn((
+            node.layout.clone(),
+            Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
+            Visibility::Visible,
+            StateScoped(InRun),
+        ));
+    }
+
+    let custom_rooms = graph.rooms.iter().filter(|node| node.template.is_some()).count();
+    info!("樓層已生成：{} 個房間（{} 個手工房間），{} 條走廊", graph.rooms.len(), custom_rooms, graph.edges.len());
+    commands.insert_resource(graph);
+}
+
+/// 玩家進入另一個房間（房間顯示、敵人生成、音樂、小地圖等系統可以監聽）
+#[derive(Event)]
+pub struct RoomEnteredEvent {
+    pub room: usize,
+}
+
+/// 房間追蹤系統 - 依玩家位置更新 CurrentRoom，換房間時送出 `RoomEnteredEvent`
+pub fn track_current_room_system(
+    player_query: Query<&Transform, With<Player>>,
+    graph: Option<Res<RoomGraph>>,
+    mut current_room: ResMut<CurrentRoom>,
+    mut entered_events: EventWriter<RoomEnteredEvent>,
+) {
+    let (Ok(player_transform), Some(graph)) = (player_query.single(), graph) else {
+        return;
+    };
+
+    if let Some(room) = graph.room_at(player_transform.translation.truncate())
+        && current_room.0 != Some(room)
+    {
+        current_room.0 = Some(room);
+        entered_events.write(RoomEnteredEvent { room });
+        let neighbors: Vec<usize> = graph.neighbors(room).collect();
+        info!("玩家進入房間 {}（相連房間: {:?}）", room, neighbors);
+    }
+}
+
+/// 房間內的敵人全部被擊倒
+#[derive(Event)]
+pub struct RoomClearedEvent {
+    pub room: usize,
+}
+
+/// 已清除房間追蹤系統 - 房間裡沒有存活的敵人就標記為已清除
+///
+/// 樓層剛生成時沒有敵人的房間直接算清除、不送出事件，之後擊倒房間最後一隻敵人才送出 `RoomClearedEvent`
+pub fn update_cleared_rooms_system(
+    enemy_query: Query<&Enemy>,
+    graph: Option<Res<RoomGraph>>,
+    mut cleared_rooms: ResMut<ClearedRooms>,
+    mut cleared_events: EventWriter<RoomClearedEvent>,
+) {
+    let Some(graph) = graph else {
+        return;
+    };
+
+    let just_generated = graph.is_added();
+    let occupied: HashSet<usize> = enemy_query.iter().map(|enemy| enemy.room).collect();
+    for room in 0..graph.rooms.len() {
+        if !occupied.contains(&room) && cleared_rooms.0.insert(room) && !just_generated {
+            cleared_events.write(RoomClearedEvent { room });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::VecDeque;
+    use crate::components::world::CompoundRoomType;
+
+    const SEEDS: u64 = 1000;
+
+    /// 專案附帶的手工樣板，讓不變量同時涵蓋程序生成與手工房間
+    fn shipped_templates() -> Vec<RoomTemplate> {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join(ROOM_TEMPLATE_DIR);
+        let mut templates: Vec<RoomTemplate> = std::fs::read_dir(dir).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(".room.ron"))
+            .map(|path| {
+                let bytes = std::fs::read(&path).unwrap();
+                RoomTemplate::from_ron(&bytes).unwrap_or_else(|error| panic!("{}: {error}", path.display()))
+            })
+            .collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    fn floor_for_seed(seed: u64, templates: &[RoomTemplate]) -> (RoomGraph, TileGrid) {
+        let graph = generate_floor_layout(&mut StdRng::seed_from_u64(seed), templates);
+        let grid = build_floor_grid(&graph);
+        (graph, grid)
+    }
+
+    /// 上鎖的門當作牆壁：不用鑰匙也要能走到每一格
+    #[test]
+    fn every_floor_tile_is_reachable_from_start() {
+        let templates = shipped_templates();
+        for seed in 0..SEEDS {
+            let (graph, grid) = floor_for_seed(seed, &templates);
+            let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+            let start = (graph.room_center(graph.start_room) / tile_size).round().as_ivec2();
+            let locked: HashSet<IVec2> = graph.edges.iter()
+                .filter(|edge| edge.locked)
+                .map(|edge| edge.door_a)
+                .collect();
+
+            let mut visited = HashSet::from([start]);
+            let mut queue = VecDeque::from([start]);
+            while let Some(cell) = queue.pop_front() {
+                for step in SLOT_DIRECTIONS {
+                    let next = cell + step;
+                    if grid.is_walkable(next) && !locked.contains(&next) && visited.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            for (cell, tile_type) in &grid.tiles {
+                if !tile_type.is_wall() && !locked.contains(cell) {
+                    assert!(visited.contains(cell), "種子 {seed} 有無法到達的格子 {cell}（{tile_type:?}）");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_room_has_a_door() {
+        let templates = shipped_templates();
+        for seed in 0..SEEDS {
+            let (graph, grid) = floor_for_seed(seed, &templates);
+            for room in 0..graph.rooms.len() {
+                let has_door = graph.doors(room)
+                    .any(|door| grid.get(door).is_some_and(|tile_type| tile_type.is_door()));
+                assert!(has_door, "種子 {seed} 的房間 {room} 沒有門");
+            }
+        }
+    }
+
+    #[test]
+    fn doors_sit_on_outer_walls() {
+        let templates = shipped_templates();
+        for seed in 0..SEEDS {
+            let (graph, grid) = floor_for_seed(seed, &templates);
+            for edge in &graph.edges {
+                for (room, door) in [(edge.a, edge.door_a), (edge.b, edge.door_b)] {
+                    let layout = &graph.rooms[room].layout;
+                    let footprint = room_footprint(layout);
+                    assert!(footprint.contains(&door), "種子 {seed} 的門 {door} 不在房間 {room} 的牆上");
+                    assert!(!layout.is_floor(door), "種子 {seed} 的門 {door} 在房間 {room} 的地板中間");
+
+                    // 門的一側通往房間內，另一側通往走廊
+                    let neighbors = SLOT_DIRECTIONS.map(|step| door + step);
+                    assert!(
+                        neighbors.iter().any(|cell| footprint.contains(cell) && grid.get(*cell) == Some(RoomTileType::Floor)),
+                        "種子 {seed} 的門 {door} 沒有通往房間 {room} 內部",
+                    );
+                    assert!(
+                        neighbors.iter().any(|cell| edge.corridor.contains(cell)),
+                        "種子 {seed} 的門 {door} 沒有接上走廊",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn corridors_and_walls_never_overlap_floors() {
+        let templates = shipped_templates();
+        for seed in 0..SEEDS {
+            let (graph, grid) = floor_for_seed(seed, &templates);
+            let footprints: HashSet<IVec2> = graph.rooms.iter()
+                .flat_map(|node| room_footprint(&node.layout))
+                .collect();
+
+            for edge in &graph.edges {
+                for cell in &edge.corridor {
+                    assert!(!footprints.contains(cell), "種子 {seed} 的走廊 {cell} 穿過房間");
+                    assert_eq!(grid.get(*cell), Some(RoomTileType::Floor), "種子 {seed} 的走廊 {cell} 被覆蓋");
+                }
+            }
+            for node in &graph.rooms {
+                for rect in &node.layout.rectangles {
+                    let (min, max) = rect.floor_cells();
+                    for y in min.y..=max.y {
+                        for x in min.x..=max.x {
+                            let tile_type = grid.get(IVec2::new(x, y));
+                            assert!(tile_type.is_some_and(|t| !t.is_wall()), "種子 {seed} 的地板 ({x}, {y}) 被牆壁覆蓋");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_tile_belongs_to_a_region() {
+        let templates = shipped_templates();
+        for seed in 0..SEEDS {
+            let (graph, grid) = floor_for_seed(seed, &templates);
+            let regions = floor_regions(&graph, &grid);
+
+            for cell in grid.tiles.keys() {
+                assert!(regions.contains_key(cell), "種子 {seed} 的格子 {cell} 不屬於任何區域");
+            }
+            for (index, edge) in graph.edges.iter().enumerate() {
+                assert_eq!(regions[&edge.door_a], FloorRegion::Corridor(index));
+                assert_eq!(regions[&edge.door_b], FloorRegion::Corridor(index));
+            }
+            for (index, node) in graph.rooms.iter().enumerate() {
+                let (min, max) = node.layout.rectangles[0].floor_cells();
+                assert_eq!(regions[&((min + max) / 2)], FloorRegion::Room(index), "種子 {seed} 的房間 {index} 中心區域錯誤");
+            }
+        }
+    }
+
+    #[test]
+    fn templates_produce_custom_rooms_with_socket_doors() {
+        let templates = shipped_templates();
+        assert!(!templates.is_empty());
+
+        let (mut custom_rooms, mut socket_doors) = (0, 0);
+        for seed in 0..100 {
+            let (graph, _) = floor_for_seed(seed, &templates);
+            for (index, node) in graph.rooms.iter().enumerate() {
+                let Some(name) = &node.template else {
+                    continue;
+                };
+                custom_rooms += 1;
+                assert_eq!(node.layout.room_type, CompoundRoomType::Custom, "樣板 {name} 應產生自訂房間");
+                socket_doors += graph.doors(index)
+                    .filter(|door| node.door_sockets.iter().any(|socket| socket.door_cell() == *door))
+                    .count();
+            }
+        }
+        assert!(custom_rooms > 0);
+        assert!(socket_doors > 0);
+    }
+
+    #[test]
+    fn start_tagged_template_is_only_used_for_start_room() {
+        let entrance = RoomTemplate::from_ron(br#"(name: "entrance", tags: ["start"], shape: Rects([(0, 0, 5, 5)]))"#).unwrap();
+        let templates = [entrance];
+        for seed in 0..200 {
+            let (graph, _) = floor_for_seed(seed, &templates);
+            for (index, node) in graph.rooms.iter().enumerate() {
+                assert_eq!(node.template.is_some(), index == graph.start_room);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_builds_same_floor() {
+        let templates = shipped_templates();
+        let (graph_a, grid_a) = floor_for_seed(42, &templates);
+        let (graph_b, grid_b) = floor_for_seed(42, &templates);
+        assert_eq!(graph_a.rooms.len(), graph_b.rooms.len());
+        assert_eq!(grid_a.tiles, grid_b.tiles);
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::player::{Player, Health, Speed, Velocity, AttackDamage};
+use crate::components::enemy::{Enemy, EnemyKind, EnemyAi, EnemyState, EnemyAttackCooldown};
+use crate::components::attack::{Hurtbox, DamageEvent, DamageSource};
+use crate::components::collision::Collider;
+use crate::components::status::StatusEffects;
+use crate::components::animation::CharacterAnimation;
+use crate::components::movement::Direction;
+use crate::resources::{
+    TileCollisionMap, RoomGraph, DungeonSeed, RngStream, GameRng,
+    CharacterMetadata, CharacterMetadataHandles,
+};
+use crate::states::InRun;
+use crate::constants::*;
+
+/// 敵人被擊倒（經驗值、掉落物等依此結算）
+#[derive(Event)]
+pub struct EnemyDefeatedEvent {
+    pub kind: EnemyKind,
+    pub room: usize,
+    pub position: Vec2,
+}
+
+/// 在每個房間內生成敵人，起始房間除外
+///
+/// 手工房間在每個生成點放一隻，其他房間在每個矩形內隨機放置
+pub fn spawn_enemies_system(
+    mut commands: Commands,
+    characters: Res<CharacterMetadataHandles>,
+    metadata: Res<Assets<CharacterMetadata>>,
+    graph: Res<RoomGraph>,
+    seed: Res<DungeonSeed>,
+) {
+    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+    let mut rng = seed.rng(RngStream::EnemySpawn);
+
+    // 描述檔在載入階段已經準備好，這裡直接取朝南的圖集 Sprite
+    let sprite_for = |kind: EnemyKind| -> (Sprite, Handle<CharacterMetadata>) {
+        let handle = characters.get(kind.character_name()).cloned().unwrap_or_default();
+        let sprite = metadata.get(&handle)
+            .map(|character| character.sprite(Direction::South))
+            .unwrap_or_default();
+        (sprite, handle)
+    };
+
+    let mut spawned = 0;
+    for (index, node) in graph.rooms.iter().enumerate() {
+        if index == graph.start_room {
+            continue;
+        }
+
+        if !node.spawn_points.is_empty() {
+            for point in &node.spawn_points {
+                let Some(rect) = node.layout.rectangles.iter().find(|rect| rect.contains_floor(*point)) else {
+                    continue;
+                };
+                let kind = random_enemy_kind(&mut rng);
+                let home = rect.floor_world_rect(tile_size);
+                spawn_enemy(&mut commands, sprite_for(kind), kind, index, point.as_vec2() * tile_size, home);
+                spawned += 1;
+            }
+            continue;
+        }
+
+        for rect in &node.layout.rectangles {
+            let home = rect.floor_world_rect(tile_size);
+            if home.width() <= 0.0 || home.height() <= 0.0 {
+                continue; // 太小的矩形沒有地板空間
+            }
+
+            let count = rng.gen_range(ENEMIES_PER_ROOM_MIN..=ENEMIES_PER_ROOM_MAX);
+            for _ in 0..count {
+                let kind = random_enemy_kind(&mut rng);
+                let position = random_point_in(&mut rng, home);
+                spawn_enemy(&mut commands, sprite_for(kind), kind, index, position, home);
+                spawned += 1;
+            }
+        }
+    }
+
+    info!("已在 {} 個房間生成 {} 隻敵人", graph.rooms.len() - 1, spawned);
+}
+
+fn random_enemy_kind(rng: &mut impl Rng) -> EnemyKind {
+    if rng.gen_bool(0.6) { EnemyKind::Slime } else { EnemyKind::Skeleton }
+}
+
+fn spawn_enemy(
+    commands: &mut Commands,
+    (sprite, metadata): (Sprite, Handle<CharacterMetadata>),
+    kind: EnemyKind,
+    room: usize,
+    position: Vec2,
+    home: Rect,
+) {
+    commands.spawn((
+        sprite,
+        CharacterAnimation::new(metadata),
+        Transform::from_translation(position.extend(Z_LAYER_PLAYER))
+            .with_scale(Vec3::splat(ENEMY_SCALE)),
+        Enemy { kind, room },
+        EnemyAi {
+            state: EnemyState::Patrol,
+            home,
+            patrol_target: position,
+            patrol_timer: Timer::from_seconds(ENEMY_PATROL_INTERVAL, TimerMode::Repeating),
+        },
+        EnemyAttackCooldown {
+            timer: Timer::from_seconds(kind.attack_cooldown(), TimerMode::Once),
+        },
+        Health {
+            current: kind.max_health(),
+            max: kind.max_health(),
+        },
+        Speed(kind.speed()),
+        Velocity(Vec2::ZERO),
+        AttackDamage(kind.damage()),
+        StatusEffects::with_immunities(kind.status_immunities()),
+        Hurtbox { radius: kind.hurtbox_radius() },
+        Collider { half_size: Vec2::splat(kind.collider_half_size()) },
+        StateScoped(InRun),
+    ));
+}
+
+fn random_point_in(rng: &mut impl Rng, rect: Rect) -> Vec2 {
+    Vec2::new(
+        rng.gen_range(rect.min.x..=rect.max.x),
+        rng.gen_range(rect.min.y..=rect.max.y),
+    )
+}
+
+/// 敵人 AI 狀態機 - 決定狀態並設定速度（套用狀態效果，暈眩時停下）
+pub fn enemy_ai_system(
+    mut enemy_query: Query<(&Enemy, &mut EnemyAi, &Transform, &Health, &Speed, &StatusEffects, &mut Velocity)>,
+    player_query: Query<&Transform, With<Player>>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let player_pos = player_query.single().ok().map(|transform| transform.translation.truncate());
+
+    for (enemy, mut ai, transform, health, speed, effects, mut velocity) in &mut enemy_query {
+        let speed = speed.0 * effects.speed_multiplier();
+        let enemy_pos = transform.translation.truncate();
+        let to_player = player_pos.map(|pos| pos - enemy_pos);
+        let distance = to_player.map_or(f32::INFINITY, |offset| offset.length());
+        let health_ratio = health.current as f32 / health.max as f32;
+
+        // 狀態轉換：逃跑 > 攻擊 > 追擊 > 巡邏
+        let new_state = if health_ratio < enemy.kind.flee_threshold() && distance < enemy.kind.sight_range() {
+            EnemyState::Flee
+        } else if distance <= enemy.kind.attack_range() {
+            EnemyState::Attack
+        } else if distance <= enemy.kind.sight_range() {
+            EnemyState::Chase
+        } else {
+            EnemyState::Patrol
+        };
+
+        if new_state != ai.state {
+            debug!("{:?} 狀態切換: {:?} -> {:?}", enemy.kind, ai.state, new_state);
+            ai.state = new_state;
+        }
+
+        velocity.0 = match ai.state {
+            EnemyState::Patrol => {
+                ai.patrol_timer.tick(time.delta());
+                let reached = enemy_pos.distance(ai.patrol_target) < 4.0;
+                if reached || ai.patrol_timer.just_finished() {
+                    ai.patrol_target = random_point_in(&mut rng.0, ai.home);
+                }
+                // 巡邏時放慢速度
+                (ai.patrol_target - enemy_pos).normalize_or_zero() * speed * 0.5
+            }
+            EnemyState::Chase => to_player.unwrap_or(Vec2::ZERO).normalize_or_zero() * speed,
+            EnemyState::Attack => Vec2::ZERO,
+            EnemyState::Flee => -to_player.unwrap_or(Vec2::ZERO).normalize_or_zero() * speed,
+        };
+    }
+}
+
+/// 敵人移動系統 - 套用速度，撞牆時沿牆滑動
+pub fn enemy_movement_system(
+    mut enemy_query: Query<(&mut Transform, &Velocity, &Collider), With<Enemy>>,
+    collision_map: Res<TileCollisionMap>,
+    time: Res<Time>,
+) {
+    for (mut transform, velocity, collider) in &mut enemy_query {
+        let (position, _) = collision_map.move_and_slide(
+            transform.translation.truncate(),
+            collider.half_size,
+            velocity.0 * time.delta_secs(),
+        );
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// 敵人攻擊系統 - 在攻擊狀態下依冷卻時間對玩家發送傷害事件，暈眩時不攻擊
+#[allow(clippy::type_complexity)]
+pub fn enemy_attack_system(
+    mut enemy_query: Query<(Entity, &Enemy, &EnemyAi, &Transform, &AttackDamage, &StatusEffects, &mut EnemyAttackCooldown)>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.single() else {
+        return;
+    };
+
+    for (enemy_entity, enemy, ai, enemy_transform, damage, effects, mut cooldown) in &mut enemy_query {
+        cooldown.timer.tick(time.delta());
+
+        if ai.state == EnemyState::Attack && cooldown.timer.finished() && !effects.is_stunned() {
+            let direction = (player_transform.translation - enemy_transform.translation)
+                .truncate()
+                .normalize_or_zero();
+            damage_events.write(DamageEvent {
+                attacker: enemy_entity,
+                target: player_entity,
+                amount: (damage.0 as f32 * effects.damage_multiplier()).round() as i32,
+                knockback: direction * ENEMY_KNOCKBACK,
+                source: DamageSource::Hit,
+            });
+            cooldown.timer.reset();
+            info!("{:?} 攻擊玩家！", enemy.kind);
+        }
+    }
+}
+
+/// 敵人死亡系統 - 血量歸零就移除
+pub fn enemy_death_system(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Enemy, &Health, &Transform)>,
+    mut defeated_events: EventWriter<EnemyDefeatedEvent>,
+) {
+    for (entity, enemy, health, transform) in &enemy_query {
+        if health.current <= 0 {
+            commands.entity(entity).despawn();
+            defeated_events.write(EnemyDefeatedEvent {
+                kind: enemy.kind,
+                room: enemy.room,
+                position: transform.translation.truncate(),
+            });
+            info!("💀 {:?} 被擊倒！", enemy.kind);
+        }
+    }
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 use crate::components::player::{Player, Health};
+use crate::states::AppState;
 
-pub fn health_system(query: Query<&Health, With<Player>>) {
+/// 玩家生命歸零時進入 GameOver
+pub fn health_system(
+    query: Query<&Health, With<Player>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
     for health in &query {
         if health.current <= 0 {
             info!("玩家死亡！");
+            next_state.set(AppState::GameOver);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use crate::components::player::{Player, Health, AttackDamage};
+use crate::components::camera::CameraImpulseEvent;
+use crate::components::attack::{Hitbox, Hurtbox, DamageEvent, DamageSource, Knockback, WeaponSwingAnimation};
+use crate::components::collision::Collider;
+use crate::components::status::{StatusEffects, ApplyStatusEvent};
+use crate::resources::TileCollisionMap;
+use crate::constants::*;
+
+/// 揮擊判定系統 - 判定框沿著揮擊弧線移動，與受擊框重疊時發送傷害與狀態效果事件
+pub fn swing_hitbox_system(
+    mut hitbox_query: Query<(&mut Hitbox, &WeaponSwingAnimation, &Transform, &ChildOf)>,
+    owner_query: Query<(&Transform, &AttackDamage, Option<&StatusEffects>)>,
+    hurtbox_query: Query<(Entity, &Transform, &Hurtbox)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    for (mut hitbox, swing, weapon_transform, child_of) in &mut hitbox_query {
+        // 不在揮擊中就重置命中紀錄，下一次揮擊可以重新命中
+        if !swing.is_attacking {
+            hitbox.hit_entities.clear();
+            continue;
+        }
+
+        let Ok((owner_transform, damage, effects)) = owner_query.get(child_of.parent()) else {
+            continue;
+        };
+        let damage_multiplier = hitbox.damage_multiplier * effects.map_or(1.0, StatusEffects::damage_multiplier);
+
+        // 武器的 Transform 是相對於玩家的，組合後取得本幀的世界座標
+        let weapon_world = owner_transform.mul_transform(*weapon_transform);
+        let hitbox_center = weapon_world
+            .transform_point(Vec3::new(hitbox.reach, 0.0, 0.0))
+            .truncate();
+        let owner_pos = owner_transform.translation.truncate();
+
+        for (target, target_transform, hurtbox) in &hurtbox_query {
+            if target == hitbox.owner || hitbox.hit_entities.contains(&target) {
+                continue;
+            }
+
+            let target_pos = target_transform.translation.truncate();
+            if hitbox_center.distance(target_pos) <= hitbox.radius + hurtbox.radius {
+                hitbox.hit_entities.insert(target);
+                damage_events.write(DamageEvent {
+                    attacker: hitbox.owner,
+                    target,
+                    amount: (damage.0 as f32 * damage_multiplier).round() as i32,
+                    knockback: (target_pos - owner_pos).normalize_or_zero() * hitbox.knockback,
+                    source: DamageSource::Hit,
+                });
+                if let Some(kind) = hitbox.status {
+                    status_events.write(ApplyStatusEvent { target, kind });
+                }
+            }
+        }
+    }
+}
+
+/// 傷害處理系統 - 扣血並套用擊退
+pub fn apply_damage_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut health_query: Query<&mut Health>,
+) {
+    for event in damage_events.read() {
+        let Ok(mut health) = health_query.get_mut(event.target) else {
+            continue; // 目標可能已被移除
+        };
+
+        health.current = (health.current - event.amount).max(0);
+        info!("💥 {:?} 對 {:?} 造成 {} 傷害，剩餘血量 {}/{}",
+              event.attacker, event.target, event.amount, health.current, health.max);
+
+        if event.knockback != Vec2::ZERO {
+            commands.entity(event.target).insert(Knockback {
+                velocity: event.knockback,
+                timer: Timer::from_seconds(KNOCKBACK_DURATION, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// 打擊感系統 - 玩家命中敵人時短暫凍結並把相機往擊退方向推，玩家受傷時相機震動（狀態效果的持續傷害除外）
+pub fn hit_feedback_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut impulse_events: EventWriter<CameraImpulseEvent>,
+    player_query: Query<(), With<Player>>,
+) {
+    for event in damage_events.read() {
+        if event.source == DamageSource::Status {
+            continue;
+        }
+        if player_query.contains(event.target) {
+            impulse_events.write(CameraImpulseEvent::Shake { trauma: PLAYER_HURT_TRAUMA });
+        } else if player_query.contains(event.attacker) {
+            impulse_events.write(CameraImpulseEvent::HitStop { duration: HIT_STOP_DURATION });
+            impulse_events.write(CameraImpulseEvent::Shake { trauma: HIT_SHAKE_TRAUMA });
+            impulse_events.write(CameraImpulseEvent::Kick { direction: event.knockback, strength: HIT_KICK_STRENGTH });
+        }
+    }
+}
+
+/// 擊退系統 - 在擊退時間內推動目標，速度逐漸衰減，有碰撞箱的目標不會被推進牆裡
+pub fn knockback_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Knockback, Option<&Collider>)>,
+    collision_map: Res<TileCollisionMap>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut knockback, collider) in &mut query {
+        knockback.timer.tick(time.delta());
+
+        let remaining = 1.0 - knockback.timer.fraction();
+        let delta = knockback.velocity * remaining * time.delta_secs();
+        let position = match collider {
+            Some(collider) => collision_map
+                .move_and_slide(transform.translation.truncate(), collider.half_size, delta)
+                .0,
+            None => transform.translation.truncate() + delta,
+        };
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+
+        if knockback.timer.finished() {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn status_damage_on_the_player_does_not_shake_the_camera() {
+        let mut world = World::new();
+        world.init_resource::<Events<DamageEvent>>();
+        world.init_resource::<Events<CameraImpulseEvent>>();
+        let player = world.spawn(Player).id();
+        let enemy = world.spawn_empty().id();
+
+        let damage = |attacker, source| DamageEvent { attacker, target: player, amount: 2, knockback: Vec2::ZERO, source };
+        world.send_event(damage(player, DamageSource::Status));
+        world.run_system_once(hit_feedback_system).unwrap();
+        assert!(world.resource::<Events<CameraImpulseEvent>>().is_empty());
+
+        world.send_event(damage(enemy, DamageSource::Hit));
+        world.run_system_once(hit_feedback_system).unwrap();
+        assert_eq!(world.resource::<Events<CameraImpulseEvent>>().len(), 1);
+    }
+}
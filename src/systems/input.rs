@@ -1,70 +1,135 @@
 use bevy::prelude::*;
 use crate::components::player::{Player, InputVector};
-use crate::components::world::{Door, RoomTile};
+use crate::resources::{ActionState, InputAction, InputBinding, InputBindings, PendingRebind};
+use crate::states::AppState;
 use crate::constants::*;
-
-#[derive(Event)]
-pub struct DoorInteractionEvent;
+use std::path::Path;
 
 #[derive(Event)]
 pub struct AttackInputEvent;
 
-pub fn input_system(
+/// 動作狀態系統 - 每幀依按鍵設定把鍵盤與手把輸入轉成動作
+pub fn update_action_state_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let gamepads: Vec<&Gamepad> = gamepads.iter().collect();
+    action_state.update(&bindings, &keyboard_input, &gamepads);
+}
+
+/// 把動作轉成玩家的移動與攻擊（互動由 interaction 系統處理）
+pub fn input_system(
+    action_state: Res<ActionState>,
     mut query: Query<&mut InputVector, With<Player>>,
-    player_query: Query<&Transform, With<Player>>,
-    door_query: Query<(&Door, &Transform), (With<RoomTile>, Without<Player>)>,
-    mut door_events: EventWriter<DoorInteractionEvent>,
     mut attack_events: EventWriter<AttackInputEvent>,
 ) {
+    // deadzone 已在 ActionState 處理（按鍵方向正規化、搖桿保留推動幅度）
     for mut input_vector in &mut query {
-        let mut raw_input = Vec2::ZERO;
+        input_vector.0 = action_state.movement();
+    }
 
-        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
-            raw_input.y += 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
-            raw_input.y -= 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
-            raw_input.x -= 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
-            raw_input.x += 1.0;
+    if action_state.just_pressed(InputAction::Attack) {
+        attack_events.write(AttackInputEvent);
+    }
+}
+
+/// 暫停畫面上的按鍵設定列表
+#[derive(Component)]
+pub struct ControlsText;
+
+pub fn spawn_controls_panel_system(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: UI_CONTROLS_FONT_SIZE, ..default() },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(32.0),
+            bottom: Val::Px(32.0),
+            ..default()
+        },
+        ControlsText,
+        StateScoped(AppState::Paused),
+    ));
+}
+
+/// 按鍵設定：暫停時按數字鍵選擇動作，再按下新的鍵盤按鍵或手把按鈕（Esc 取消）
+pub fn rebind_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut bindings: ResMut<InputBindings>,
+    mut pending: ResMut<PendingRebind>,
+) {
+    let Some(action) = pending.0 else {
+        let selected = keyboard_input.get_just_pressed().find_map(|key| rebind_key_index(*key));
+        if let Some(action) = selected.and_then(|index| InputAction::ALL.get(index)) {
+            pending.0 = Some(*action);
         }
+        return;
+    };
 
-        // deadzone 檢查在正規化之前，避免微小輸入被放大
-        input_vector.0 = if raw_input.length() > INPUT_DEADZONE {
-            raw_input.normalize()  // 只有超過 deadzone 才正規化
-        } else {
-            Vec2::ZERO
-        };
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        pending.0 = None;
+        return;
     }
 
-    // 檢測空白鍵按下事件 - 優先處理門交互
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        // 檢查玩家是否在門附近
-        let player_transform = match player_query.single() {
-            Ok(transform) => transform,
-            Err(_) => return,
-        };
-        
-        let mut near_door = false;
-        let interaction_distance = ROOM_TILE_SIZE * PLAYER_SCALE * 10.0; // 10個瓷磚的距離，約480像素交互範圍
-        
-        for (_door, door_transform) in &door_query {
-            let distance = player_transform.translation.distance(door_transform.translation);
-            if distance <= interaction_distance {
-                near_door = true;
-                break;
-            }
-        }
-        
-        // 根據是否在門附近發送不同事件
-        if near_door {
-            door_events.write(DoorInteractionEvent);
-        } else {
-            attack_events.write(AttackInputEvent);
+    let binding = keyboard_input.get_just_pressed().next().map(|key| InputBinding::Key(*key))
+        .or_else(|| gamepads.iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next())
+            .map(|button| InputBinding::Gamepad(*button)));
+    let Some(binding) = binding else {
+        return;
+    };
+
+    let swapped = bindings.rebind(action, binding);
+    pending.0 = None;
+    info!("{:?} 已綁定到 {}", action, binding);
+    if let Some(other) = swapped {
+        info!("{:?} 原本使用這個按鍵，改為 {}", other, bindings.describe(other));
+    }
+    if let Err(error) = bindings.write(Path::new(INPUT_CONFIG_PATH)) {
+        error!("無法儲存按鍵設定：{}", error);
+    }
+}
+
+// 選擇動作的按鍵與顯示名稱：第十個動作用 0，之後依序用 -、=、[
+// 長度跟著 InputAction::ALL，新增動作時沒有補上選擇按鍵就無法編譯
+const REBIND_KEYS: [(KeyCode, &str); InputAction::ALL.len()] = [
+    (KeyCode::Digit1, "1"), (KeyCode::Digit2, "2"), (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"), (KeyCode::Digit5, "5"), (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"), (KeyCode::Digit8, "8"), (KeyCode::Digit9, "9"),
+    (KeyCode::Digit0, "0"), (KeyCode::Minus, "-"), (KeyCode::Equal, "="),
+    (KeyCode::BracketLeft, "["),
+];
+
+fn rebind_key_index(key: KeyCode) -> Option<usize> {
+    REBIND_KEYS.iter().position(|(rebind_key, _)| *rebind_key == key)
+}
+
+pub fn update_controls_text_system(
+    bindings: Res<InputBindings>,
+    pending: Res<PendingRebind>,
+    mut text_query: Query<&mut Text, With<ControlsText>>,
+) {
+    let mut lines: Vec<String> = InputAction::ALL.iter().zip(REBIND_KEYS)
+        .map(|(action, (_, key))| format!("{} {}: {}", key, action.label(), bindings.describe(*action)))
+        .collect();
+    lines.push(match pending.0 {
+        Some(action) => format!("Press a key or button for {} (Esc to cancel)", action.label()),
+        None => "Press 0-9, -, = or [ to rebind".to_string(),
+    });
+    let content = lines.join("\n");
+
+    for mut text in &mut text_query {
+        if text.0 != content {
+            text.0.clone_from(&content);
         }
     }
-}
\ No newline at end of file
+}
+
+/// 離開暫停畫面時取消未完成的按鍵設定
+pub fn cancel_rebind_system(mut pending: ResMut<PendingRebind>) {
+    pending.0 = None;
+}
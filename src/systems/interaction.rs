@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use crate::components::player::Player;
+use crate::components::attack::PlayerFacing;
+use crate::components::interaction::{Interactable, InteractEvent, InteractionPrompt};
+use crate::resources::{ActionState, InputAction, InputBindings, InteractionTarget};
+use crate::states::InRun;
+use crate::constants::*;
+
+/// 互動目標系統 - 找出玩家在互動距離內最近的物件（需要面向的物件必須在玩家前方）
+pub fn find_interaction_target_system(
+    player_query: Query<(&Transform, &PlayerFacing), With<Player>>,
+    interactable_query: Query<(Entity, &Interactable, &Transform), Without<Player>>,
+    mut target: ResMut<InteractionTarget>,
+) {
+    let Ok((player_transform, facing)) = player_query.single() else {
+        target.0 = None;
+        return;
+    };
+    let player_position = player_transform.translation.truncate();
+
+    target.0 = interactable_query.iter()
+        .filter_map(|(entity, interactable, transform)| {
+            let offset = transform.translation.truncate() - player_position;
+            let distance = offset.length();
+            let in_range = distance <= interactable.radius;
+            let facing_it = !interactable.requires_facing
+                || facing.direction.dot(offset.normalize_or_zero()) >= INTERACTION_FACING_MIN_DOT;
+            (in_range && facing_it).then_some((entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+}
+
+/// 按下互動鍵時對目前的互動目標送出事件
+pub fn interact_input_system(
+    action_state: Res<ActionState>,
+    target: Res<InteractionTarget>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    if let Some(entity) = target.0
+        && action_state.just_pressed(InputAction::Interact)
+    {
+        interact_events.write(InteractEvent { target: entity });
+    }
+}
+
+pub fn spawn_interaction_prompt_system(mut commands: Commands) {
+    commands.spawn((
+        Text2d::new(""),
+        TextFont { font_size: UI_PROMPT_FONT_SIZE, ..default() },
+        TextColor(Color::WHITE),
+        Transform::from_xyz(0.0, 0.0, Z_LAYER_PROMPT),
+        Visibility::Hidden,
+        InteractionPrompt,
+        StateScoped(InRun),
+    ));
+}
+
+/// 在互動目標上方顯示「[按鍵] 動作」提示
+pub fn update_interaction_prompt_system(
+    target: Res<InteractionTarget>,
+    bindings: Res<InputBindings>,
+    interactable_query: Query<(&Interactable, &Transform), Without<InteractionPrompt>>,
+    mut prompt_query: Query<(&mut Text2d, &mut Transform, &mut Visibility), With<InteractionPrompt>>,
+) {
+    let Ok((mut text, mut transform, mut visibility)) = prompt_query.single_mut() else {
+        return;
+    };
+
+    let Some((interactable, target_transform)) = target.0.and_then(|entity| interactable_query.get(entity).ok()) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let key = bindings.get(InputAction::Interact).first()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "?".to_string());
+    let content = format!("[{}] {}", key, interactable.prompt);
+    if text.0 != content {
+        text.0 = content;
+    }
+    transform.translation.x = target_transform.translation.x;
+    transform.translation.y = target_transform.translation.y + INTERACTION_PROMPT_OFFSET;
+    *visibility = Visibility::Visible;
+}
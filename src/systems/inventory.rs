@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+use crate::components::player::{Player, Health};
+use crate::components::attack::WeaponType;
+use crate::components::inventory::{Inventory, ItemKind, ItemPickup, InventoryEvent};
+use crate::components::interaction::{Interactable, InteractEvent};
+use crate::components::status::{StatusEffects, StatusKind};
+use crate::resources::{RoomGraph, DungeonSeed, RngStream, GameRng, CollectedItems, ActionState, InputAction};
+use crate::systems::enemy::EnemyDefeatedEvent;
+use crate::states::InRun;
+use crate::constants::*;
+
+/// 在房間內放置物品：魔杖與弓各放在一個隨機房間，其他房間有機會放藥水或金幣
+///
+/// 起始房間不放；同一個種子放置的位置相同，讀檔時再移除已經撿起的
+pub fn spawn_room_items_system(mut commands: Commands, graph: Res<RoomGraph>, seed: Res<DungeonSeed>) {
+    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
+    let mut rng = seed.rng(RngStream::ItemSpawn);
+    let rooms: Vec<usize> = (0..graph.rooms.len()).filter(|room| *room != graph.start_room).collect();
+    if rooms.is_empty() {
+        return;
+    }
+
+    let mut placements = Vec::new();
+    for weapon in [WeaponType::Magic, WeaponType::Arrow] {
+        placements.push((rooms[rng.gen_range(0..rooms.len())], ItemKind::Weapon(weapon), 1));
+    }
+    for &room in &rooms {
+        if !rng.gen_bool(ROOM_ITEM_CHANCE) {
+            continue;
+        }
+        if rng.gen_bool(0.5) {
+            placements.push((room, ItemKind::Potion, 1));
+        } else {
+            placements.push((room, ItemKind::Gold, rng.gen_range(ROOM_GOLD_AMOUNT.0..=ROOM_GOLD_AMOUNT.1)));
+        }
+    }
+
+    // 在房間第一個矩形的地板上選格子，同一格只放一個
+    let mut used = HashSet::new();
+    let mut spawned = 0;
+    for (room, item, count) in placements {
+        let (min, max) = graph.rooms[room].layout.rectangles[0].floor_cells();
+        if min.cmpgt(max).any() {
+            continue; // 太小的矩形沒有地板空間
+        }
+        let cell = IVec2::new(rng.gen_range(min.x..=max.x), rng.gen_range(min.y..=max.y));
+        if used.insert(cell) {
+            spawn_pickup(&mut commands, item, count, room, Some(cell), cell.as_vec2() * tile_size);
+            spawned += 1;
+        }
+    }
+
+    info!("已在房間內放置 {} 個物品", spawned);
+}
+
+/// 敵人掉落物 - 依敵人的掉落表各自判定，多個掉落物圍著倒下的位置排開
+pub fn drop_enemy_loot_system(
+    mut commands: Commands,
+    mut defeated_events: EventReader<EnemyDefeatedEvent>,
+    mut rng: ResMut<GameRng>,
+) {
+    for event in defeated_events.read() {
+        let drops: Vec<(ItemKind, u32)> = event.kind.loot().iter()
+            .filter(|(_, _, chance)| rng.0.gen_bool(*chance))
+            .map(|(item, count, _)| (*item, *count))
+            .collect();
+
+        for (index, (item, count)) in drops.iter().enumerate() {
+            let offset = if drops.len() > 1 {
+                Vec2::from_angle(TAU * index as f32 / drops.len() as f32) * ITEM_DROP_SCATTER
+            } else {
+                Vec2::ZERO
+            };
+            spawn_pickup(&mut commands, *item, *count, event.room, None, event.position + offset);
+        }
+    }
+}
+
+/// 生成地上的物品；需要按互動鍵撿起的物品加上互動目標
+pub fn spawn_pickup(
+    commands: &mut Commands,
+    item: ItemKind,
+    count: u32,
+    room: usize,
+    origin: Option<IVec2>,
+    position: Vec2,
+) {
+    let mut entity = commands.spawn((
+        Sprite::from_color(item.color(), Vec2::splat(ITEM_SIZE)),
+        Transform::from_translation(position.extend(Z_LAYER_ITEM)),
+        ItemPickup { item, count, room, origin },
+        StateScoped(InRun),
+    ));
+    if !item.auto_collect() {
+        entity.insert(Interactable {
+            radius: ITEM_INTERACTION_RADIUS,
+            prompt: format!("Pick up {}", item.label()),
+            requires_facing: false,
+        });
+    }
+}
+
+/// 撿起物品系統 - 自動撿起的物品走到旁邊就撿，其他物品按互動鍵撿
+///
+/// 物品欄放不下的部分留在地上
+pub fn collect_pickups_system(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut Inventory), With<Player>>,
+    mut pickup_query: Query<(Entity, &mut ItemPickup, &Transform), Without<Player>>,
+    mut interact_events: EventReader<InteractEvent>,
+    mut inventory_events: EventWriter<InventoryEvent>,
+    mut collected: ResMut<CollectedItems>,
+) {
+    let Ok((player_transform, mut inventory)) = player_query.single_mut() else {
+        return;
+    };
+    let interacted: Vec<Entity> = interact_events.read().map(|event| event.target).collect();
+    let player_position = player_transform.translation.truncate();
+
+    for (entity, mut pickup, transform) in &mut pickup_query {
+        let wanted = if pickup.item.auto_collect() {
+            player_position.distance(transform.translation.truncate()) <= ITEM_PICKUP_RADIUS
+        } else {
+            interacted.contains(&entity)
+        };
+        if !wanted || inventory.count(pickup.item) >= pickup.item.max_stack() {
+            continue;
+        }
+
+        let added = inventory.add(pickup.item, pickup.count);
+        pickup.count -= added;
+        inventory_events.write(InventoryEvent::Added { item: pickup.item, count: added });
+        info!("🎒 撿起 {} x{}", pickup.item.label(), added);
+
+        if pickup.count == 0 {
+            commands.entity(entity).despawn();
+            if let Some(cell) = pickup.origin {
+                collected.0.insert(cell);
+            }
+        }
+    }
+}
+
+/// 使用物品 - 受傷時喝一瓶藥水回復生命，之後短暫再生
+pub fn use_item_system(
+    action_state: Res<ActionState>,
+    mut player_query: Query<(&mut Health, &mut Inventory, &mut StatusEffects), With<Player>>,
+    mut inventory_events: EventWriter<InventoryEvent>,
+) {
+    if !action_state.just_pressed(InputAction::UseItem) {
+        return;
+    }
+    let Ok((mut health, mut inventory, mut effects)) = player_query.single_mut() else {
+        return;
+    };
+
+    if health.current >= health.max {
+        info!("生命已滿，不需要喝藥水");
+    } else if inventory.remove(ItemKind::Potion, 1) {
+        health.current = (health.current + POTION_HEAL).min(health.max);
+        effects.apply(StatusKind::Regen);
+        inventory_events.write(InventoryEvent::Used { item: ItemKind::Potion });
+        info!("🧪 喝下藥水，生命 {}/{}", health.current, health.max);
+    } else {
+        info!("沒有藥水");
+    }
+}
+
+/// 畫面左上角的物品欄，記錄最近一次的變化
+#[derive(Component, Default)]
+pub struct InventoryText {
+    last_change: String,
+}
+
+pub fn spawn_inventory_hud_system(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: UI_HUD_FONT_SIZE, ..default() },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(16.0),
+            ..default()
+        },
+        InventoryText::default(),
+        StateScoped(InRun),
+    ));
+}
+
+/// 更新物品欄文字，第二行顯示最近一次的變化
+pub fn update_inventory_hud_system(
+    mut inventory_events: EventReader<InventoryEvent>,
+    player_query: Query<&Inventory, With<Player>>,
+    mut text_query: Query<(&mut Text, &mut InventoryText)>,
+) {
+    let last_event = inventory_events.read().last().copied();
+    let Ok(inventory) = player_query.single() else {
+        return;
+    };
+
+    for (mut text, mut hud) in &mut text_query {
+        if let Some(event) = last_event {
+            hud.last_change = match event {
+                InventoryEvent::Added { item, count } => format!("+{} {}", count, item.label()),
+                InventoryEvent::Removed { item, count } => format!("-{} {}", count, item.label()),
+                InventoryEvent::Used { item } => format!("Used {}", item.label()),
+            };
+        }
+
+        let content = format!(
+            "Gold {}  Potions {}  Keys {}\n{}",
+            inventory.count(ItemKind::Gold),
+            inventory.count(ItemKind::Potion),
+            inventory.count(ItemKind::Key),
+            hud.last_change,
+        );
+        if text.0 != content {
+            text.0 = content;
+        }
+    }
+}
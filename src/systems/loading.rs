@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::resources::{
+    AssetManifest, RoomAssets, RoomTemplateFolder,
+    CombatSprites, CharacterMetadataHandles, WeaponCatalog,
+};
+use crate::systems::state::spawn_screen;
+use crate::states::AppState;
+use crate::constants::*;
+
+/// 載入畫面上的進度文字
+#[derive(Component)]
+pub struct LoadingProgressText;
+
+/// 啟動時開始載入所有資源，之後每一局共用同一份 Handle
+pub fn load_game_assets_system(mut commands: Commands, asset_server: Res<AssetServer>, weapons: Res<WeaponCatalog>) {
+    let mut manifest = AssetManifest::default();
+
+    commands.insert_resource(RoomAssets::load_all(&asset_server, &mut manifest));
+    commands.insert_resource(CombatSprites::load_all(&asset_server, &mut manifest, &weapons));
+    commands.insert_resource(CharacterMetadataHandles::load_all(&asset_server, &mut manifest));
+    // 手工房間樣板是選用的：個別樣板載入失敗只會被略過，不列入必要清單
+    commands.insert_resource(RoomTemplateFolder(asset_server.load_folder(ROOM_TEMPLATE_DIR)));
+
+    commands.insert_resource(manifest);
+}
+
+pub fn spawn_loading_screen_system(mut commands: Commands) {
+    let background = Color::srgb(MENU_BACKGROUND_COLOR.0, MENU_BACKGROUND_COLOR.1, MENU_BACKGROUND_COLOR.2);
+    let hint = spawn_screen(&mut commands, AppState::Loading, "Loading", "", background);
+    commands.entity(hint).insert(LoadingProgressText);
+}
+
+/// 更新載入進度；有檔案載入失敗時列出缺少的檔案，而不是畫出空白的畫面
+pub fn loading_progress_system(
+    manifest: Res<AssetManifest>,
+    asset_server: Res<AssetServer>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<LoadingProgressText>>,
+    mut reported: Local<bool>,
+) {
+    let progress = manifest.progress(&asset_server);
+
+    let (message, color) = if progress.failed.is_empty() {
+        let message = if progress.is_complete() {
+            "Generating dungeon...".to_string()
+        } else {
+            format!("Loading assets... {}/{}", progress.loaded, progress.total)
+        };
+        (message, Color::srgb(0.8, 0.8, 0.8))
+    } else {
+        if !*reported {
+            for (path, error) in &progress.failed {
+                error!("缺少資源檔案 {}：{}", path, error);
+            }
+            *reported = true;
+        }
+        let paths: Vec<&str> = progress.failed.iter().map(|(path, _)| path.as_str()).collect();
+        (format!("Missing asset files:\n{}", paths.join("\n")), Color::srgb(0.9, 0.3, 0.3))
+    };
+
+    for (mut text, mut text_color) in &mut text_query {
+        text.0.clone_from(&message);
+        text_color.0 = color;
+    }
+}
+
+/// 必要資源都已載入完成
+pub fn assets_loaded(manifest: Res<AssetManifest>, asset_server: Res<AssetServer>) -> bool {
+    manifest.progress(&asset_server).is_complete()
+}
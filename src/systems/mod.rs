@@ -10,6 +10,18 @@ pub mod visual_combat;  // 新的視覺化戰鬥系統
 pub mod door_interaction;  // 門交互系統
 pub mod wall_collision;  // 牆壁碰撞檢測系統
 pub mod room_transition;  // 房間切換系統
+pub mod enemy;  // 敵人 AI 與戰鬥系統
+pub mod hitbox;  // 命中判定與傷害系統
+pub mod dungeon;  // 樓層生成系統
+pub mod state;  // 遊戲流程狀態系統
+pub mod loading;  // 資源載入系統
+pub mod save;  // 存檔與讀檔系統
+pub mod interaction;  // 互動系統
+pub mod animation;  // 角色動畫系統
+pub mod progression;  // 經驗值與等級系統
+pub mod inventory;  // 物品欄與撿拾系統
+pub mod projectile;  // 投射物系統
+pub mod status;  // 狀態效果系統
 
 // Re-export all systems for easy importing
 pub use attack::*;
@@ -22,4 +34,16 @@ pub use setup::*;
 pub use visual_combat::*;
 pub use door_interaction::*;
 pub use wall_collision::*;
-pub use room_transition::*;
\ No newline at end of file
+pub use room_transition::*;
+pub use enemy::*;
+pub use hitbox::*;
+pub use dungeon::*;
+pub use state::*;
+pub use loading::*;
+pub use save::*;
+pub use interaction::*;
+pub use animation::*;
+pub use progression::*;
+pub use inventory::*;
+pub use projectile::*;
+pub use status::*;
\ No newline at end of file
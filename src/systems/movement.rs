@@ -1,35 +1,73 @@
 use bevy::prelude::*;
-use crate::components::player::{Player, Velocity, InputVector, Speed};
-use crate::components::movement::Direction;
-use crate::resources::sprites::DirectionSpriteHandles;
+use crate::components::player::{Player, Velocity, InputVector, Speed, Dash, DashCooldown};
+use crate::components::attack::PlayerFacing;
+use crate::components::collision::Collider;
+use crate::components::status::StatusEffects;
+use crate::resources::{TileCollisionMap, ActionState, InputAction};
+use crate::constants::*;
 
-pub fn movement_system(
-    mut query: Query<(&mut Transform, &mut Velocity, &InputVector, &Speed), With<Player>>,
+/// 衝刺系統 - 按衝刺鍵朝移動方向（沒有移動時朝面向）短暫高速移動，暈眩時不能衝刺
+#[allow(clippy::type_complexity)]
+pub fn player_dash_system(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    mut query: Query<(Entity, &InputVector, &PlayerFacing, &StatusEffects, &mut DashCooldown, Option<&mut Dash>), With<Player>>,
     time: Res<Time>,
 ) {
-    for (mut transform, mut velocity, input_vector, speed) in &mut query {
-        // 根據正規化的輸入向量和速度計算實際速度
-        velocity.0 = input_vector.0 * speed.0;
+    for (entity, input, facing, effects, mut cooldown, dash) in &mut query {
+        cooldown.timer.tick(time.delta());
 
-        // 應用移動
-        transform.translation += velocity.0.extend(0.0) * time.delta_secs();
+        if let Some(mut dash) = dash {
+            if dash.timer.tick(time.delta()).finished() {
+                commands.entity(entity).remove::<Dash>();
+            }
+            continue;
+        }
+
+        if action_state.just_pressed(InputAction::Dash) && cooldown.timer.finished() && !effects.is_stunned() {
+            let direction = if input.0.length() > 0.1 { input.0.normalize() } else { facing.direction };
+            commands.entity(entity).insert(Dash {
+                direction,
+                timer: Timer::from_seconds(DASH_DURATION, TimerMode::Once),
+            });
+            cooldown.timer = Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once);
+        }
     }
 }
 
-pub fn sprite_direction_system(
-    mut query: Query<(&InputVector, &mut Direction, &mut Sprite), With<Player>>,
-    sprite_handles: Res<DirectionSpriteHandles>,
+/// 玩家移動系統 - 依輸入與速度（套用狀態效果）移動，衝刺中改用衝刺的方向與速度，撞牆時沿牆滑動
+#[allow(clippy::type_complexity)]
+pub fn player_movement_system(
+    mut query: Query<(&mut Transform, &mut Velocity, &InputVector, &Speed, &StatusEffects, &Collider, Option<&Dash>), With<Player>>,
+    collision_map: Res<TileCollisionMap>,
+    time: Res<Time>,
 ) {
-    for (input_vector, mut direction, mut sprite) in &mut query {
-        // 使用輸入向量而非速度來決定方向，停下來時保持原方向
-        if let Some(new_direction) = Direction::from_input_vector(input_vector.0) {
-            if *direction != new_direction {
-                *direction = new_direction;
-                if let Some(handle) = sprite_handles.handles.get(&new_direction) {
-                    sprite.image = handle.clone();
-                }
-            }
+    for (mut transform, mut velocity, input, speed, effects, collider, dash) in &mut query {
+        let dashing = dash.filter(|dash| !dash.timer.finished());
+        velocity.0 = match dashing {
+            Some(dash) => dash.direction * DASH_SPEED,
+            None => input.0 * speed.0,
+        } * effects.speed_multiplier();
+        if dashing.is_some() || input.0.length() > 0.1 {
+            // 沿牆滑動：被擋住的軸停下，另一軸繼續移動
+            let (position, _) = collision_map.move_and_slide(
+                transform.translation.truncate(),
+                collider.half_size,
+                velocity.0 * time.delta_secs(),
+            );
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
         }
-        // 如果沒有輸入（返回 None），保持當前方向不變
     }
-}
\ No newline at end of file
+}
+
+/// 玩家面向系統 - 記錄最後移動方向，停下來時保持原方向
+pub fn update_player_facing_system(
+    mut player_query: Query<(&mut PlayerFacing, &InputVector), With<Player>>,
+) {
+    for (mut facing, input) in &mut player_query {
+        if input.0.length() > 0.1 {
+            facing.direction = input.0.normalize();
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use crate::components::player::{Player, Experience, Health, AttackDamage, Speed};
+use crate::components::animation::CharacterAnimation;
+use crate::components::camera::CameraImpulseEvent;
+use crate::resources::{PlayerArchetype, CharacterMetadata, CharacterMetadataHandles};
+use crate::systems::enemy::EnemyDefeatedEvent;
+use crate::constants::*;
+
+/// 玩家升級（UI 與特效依此顯示），一次升多級時每一級各送一個
+#[derive(Event)]
+pub struct LevelUpEvent {
+    pub level: u32,
+}
+
+/// 經驗值系統 - 擊倒敵人時累積經驗值，達到等級表的門檻就升級
+pub fn gain_experience_system(
+    mut defeated_events: EventReader<EnemyDefeatedEvent>,
+    mut player_query: Query<&mut Experience, With<Player>>,
+    archetype: Res<PlayerArchetype>,
+    mut level_up_events: EventWriter<LevelUpEvent>,
+) {
+    let Ok(mut experience) = player_query.single_mut() else {
+        return;
+    };
+
+    for event in defeated_events.read() {
+        let previous = experience.level;
+        *experience = add_experience(*experience, event.kind.experience(), &archetype);
+        for level in previous + 1..=experience.level {
+            level_up_events.write(LevelUpEvent { level });
+        }
+    }
+}
+
+/// 升級回饋 - 記錄並輕微震動畫面
+pub fn level_up_feedback_system(
+    mut level_up_events: EventReader<LevelUpEvent>,
+    mut impulse_events: EventWriter<CameraImpulseEvent>,
+) {
+    for event in level_up_events.read() {
+        info!("⭐ 升到 {} 級！", event.level);
+        impulse_events.write(CameraImpulseEvent::Shake { trauma: LEVEL_UP_TRAUMA });
+    }
+}
+
+/// 能力值系統 - 經驗值改變（包含生成與讀檔）時套用目前等級的能力值與角色圖片
+///
+/// 最大生命提高的部分同時補回目前生命
+#[allow(clippy::type_complexity)]
+pub fn apply_level_stats_system(
+    mut player_query: Query<
+        (Ref<Experience>, &mut Health, &mut AttackDamage, &mut Speed, &mut CharacterAnimation, &mut Sprite),
+        With<Player>,
+    >,
+    archetype: Res<PlayerArchetype>,
+    characters: Res<CharacterMetadataHandles>,
+    metadata: Res<Assets<CharacterMetadata>>,
+) {
+    let Ok((experience, mut health, mut damage, mut speed, mut animation, mut sprite)) = player_query.single_mut() else {
+        return;
+    };
+    if !experience.is_changed() {
+        return;
+    }
+
+    let stats = archetype.level(experience.level);
+    if health.max != stats.max_health {
+        health.current = (health.current + stats.max_health - health.max).clamp(1, stats.max_health);
+        health.max = stats.max_health;
+    }
+    damage.0 = stats.attack_damage;
+    speed.0 = stats.speed;
+
+    // 這個等級使用不同的角色時換成它的圖集
+    if let Some(handle) = characters.get(stats.character)
+        && animation.metadata != *handle
+    {
+        animation.metadata = handle.clone();
+        if let Some(character) = metadata.get(handle) {
+            sprite.image = character.atlas.clone();
+            sprite.texture_atlas = Some(TextureAtlas { layout: character.layout.clone(), index: 0 });
+        }
+    }
+}
+
+/// 加上經驗值並依等級表計算等級（等級不會下降）
+fn add_experience(experience: Experience, amount: u32, archetype: &PlayerArchetype) -> Experience {
+    let xp = experience.xp.saturating_add(amount);
+    Experience {
+        level: archetype.level_for_xp(xp).max(experience.level),
+        xp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn experience_can_skip_levels_and_stops_at_the_top() {
+        let archetype = PlayerArchetype::default();
+        let start = Experience::default();
+
+        assert_eq!(add_experience(start, 10, &archetype), Experience { level: 1, xp: 10 });
+        assert_eq!(add_experience(start, 130, &archetype), Experience { level: 3, xp: 130 });
+
+        let top = add_experience(start, u32::MAX, &archetype);
+        assert_eq!(top.level, archetype.levels.len() as u32);
+        assert_eq!(add_experience(top, 1, &archetype).xp, u32::MAX);
+    }
+}
@@ -0,0 +1,243 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::attack::{Projectile, ImpactEffect, Hitbox, Hurtbox, DamageEvent, DamageSource, WeaponType};
+use crate::components::status::ApplyStatusEvent;
+use crate::resources::{TileCollisionMap, WeaponCatalog, WeaponDefinition, WeaponAttack, CombatSprites};
+use crate::states::InRun;
+use crate::constants::*;
+
+/// 發射投射物：多個投射物以面向為中心平均分布在設定的角度內（近戰武器不發射）
+pub fn spawn_projectiles(
+    commands: &mut Commands,
+    owner: Entity,
+    origin: Vec2,
+    direction: Vec2,
+    damage: i32,
+    weapon_type: WeaponType,
+    weapon: &WeaponDefinition,
+) {
+    let WeaponAttack::Projectile(definition) = &weapon.attack else {
+        return;
+    };
+    for direction in spread_directions(direction, definition.count, definition.spread) {
+        commands.spawn((
+            Sprite::from_color(definition.color, definition.size),
+            Transform::from_translation(origin.extend(Z_LAYER_PROJECTILE))
+                .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+            Projectile {
+                velocity: direction * definition.speed,
+                lifetime: Timer::from_seconds(definition.lifetime, TimerMode::Once),
+                damage,
+                weapon_type,
+                pierce: definition.pierce,
+                bounces: definition.bounces,
+            },
+            Hitbox {
+                owner,
+                reach: 0.0,
+                radius: definition.radius,
+                knockback: definition.knockback,
+                damage_multiplier: 1.0,  // 傷害已算在 Projectile.damage
+                status: weapon.on_hit,
+                hit_entities: HashSet::new(),
+            },
+            StateScoped(InRun),
+        ));
+    }
+}
+
+/// 投射物移動系統 - 追蹤最近的目標、撞到牆或關閉的門時反彈或消失，超過存在時間就消失
+#[allow(clippy::type_complexity)]
+pub fn projectile_movement_system(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform, &Hitbox)>,
+    target_query: Query<(Entity, &Transform), (With<Hurtbox>, Without<Projectile>)>,
+    collision_map: Res<TileCollisionMap>,
+    catalog: Res<WeaponCatalog>,
+    sprites: Res<CombatSprites>,
+    time: Res<Time>,
+) {
+    for (entity, mut projectile, mut transform, hitbox) in &mut projectile_query {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let WeaponAttack::Projectile(definition) = &catalog.get(projectile.weapon_type).attack else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+
+        // 追蹤：朝範圍內最近的目標轉向，每秒最多轉 homing 弧度
+        if definition.homing > 0.0 {
+            let target = target_query.iter()
+                .filter(|(target, _)| *target != hitbox.owner)
+                .map(|(_, target_transform)| target_transform.translation.truncate())
+                .filter(|target| target.distance(position) <= PROJECTILE_HOMING_RANGE)
+                .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+            if let Some(target) = target {
+                let max_turn = definition.homing * time.delta_secs();
+                projectile.velocity = steer_towards(projectile.velocity, target - position, max_turn);
+            }
+        }
+
+        let delta = projectile.velocity * time.delta_secs();
+        let (moved, blocked) = collision_map.move_and_slide(position, Vec2::splat(PROJECTILE_WALL_HALF_SIZE), delta);
+        if blocked.any() {
+            if projectile.bounces == 0 {
+                spawn_impact(&mut commands, projectile.weapon_type, &catalog, &sprites, moved);
+                commands.entity(entity).despawn();
+                continue;
+            }
+            projectile.bounces -= 1;
+            projectile.velocity = reflect(projectile.velocity, blocked);
+        }
+
+        transform.translation.x = moved.x;
+        transform.translation.y = moved.y;
+        transform.rotation = Quat::from_rotation_z(projectile.velocity.to_angle());
+    }
+}
+
+/// 投射物命中系統 - 碰到受擊框就造成傷害與附加狀態效果，穿透次數用完後消失
+#[allow(clippy::too_many_arguments)]
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Hitbox, &Transform)>,
+    hurtbox_query: Query<(Entity, &Transform, &Hurtbox)>,
+    catalog: Res<WeaponCatalog>,
+    sprites: Res<CombatSprites>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    for (entity, mut projectile, mut hitbox, transform) in &mut projectile_query {
+        let position = transform.translation.truncate();
+
+        for (target, target_transform, hurtbox) in &hurtbox_query {
+            let target_pos = target_transform.translation.truncate();
+            if target == hitbox.owner
+                || hitbox.hit_entities.contains(&target)
+                || position.distance(target_pos) > hitbox.radius + hurtbox.radius
+            {
+                continue;
+            }
+
+            hitbox.hit_entities.insert(target);
+            damage_events.write(DamageEvent {
+                attacker: hitbox.owner,
+                target,
+                amount: projectile.damage,
+                knockback: projectile.velocity.normalize_or_zero() * hitbox.knockback,
+                source: DamageSource::Hit,
+            });
+            if let Some(kind) = hitbox.status {
+                status_events.write(ApplyStatusEvent { target, kind });
+            }
+            spawn_impact(&mut commands, projectile.weapon_type, &catalog, &sprites, target_pos);
+
+            if projectile.pierce == 0 {
+                commands.entity(entity).despawn();
+                break;
+            }
+            projectile.pierce -= 1;
+        }
+    }
+}
+
+/// 命中效果系統 - 放大並淡出，時間到就消失
+pub fn update_impact_effects_system(
+    mut commands: Commands,
+    mut effect_query: Query<(Entity, &mut ImpactEffect, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut effect, mut transform, mut sprite) in &mut effect_query {
+        effect.timer.tick(time.delta());
+        if effect.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = effect.timer.fraction();
+        transform.scale = Vec3::splat(1.0 + progress * 0.5);
+        sprite.color.set_alpha(1.0 - progress);
+    }
+}
+
+/// 在命中或撞牆的位置生成武器的命中效果；沒有效果圖片時用色塊代替
+fn spawn_impact(
+    commands: &mut Commands,
+    weapon_type: WeaponType,
+    catalog: &WeaponCatalog,
+    sprites: &CombatSprites,
+    position: Vec2,
+) {
+    let WeaponAttack::Projectile(definition) = &catalog.get(weapon_type).attack else {
+        return;
+    };
+    let impact = &definition.impact;
+    let sprite = match sprites.impacts.get(&weapon_type) {
+        Some(image) => Sprite {
+            image: image.clone(),
+            custom_size: Some(impact.size),
+            ..default()
+        },
+        None => Sprite::from_color(impact.color, impact.size),
+    };
+
+    commands.spawn((
+        sprite,
+        Transform::from_translation(position.extend(Z_LAYER_PROJECTILE)),
+        ImpactEffect { timer: Timer::from_seconds(impact.duration, TimerMode::Once) },
+        StateScoped(InRun),
+    ));
+}
+
+/// 以 direction 為中心，在 spread 弧度內平均分布 count 個方向
+fn spread_directions(direction: Vec2, count: u32, spread: f32) -> impl Iterator<Item = Vec2> {
+    // 只有一個時直接朝 direction
+    let (start, step) = if count > 1 { (-spread / 2.0, spread / (count - 1) as f32) } else { (0.0, 0.0) };
+    (0..count).map(move |index| Vec2::from_angle(start + step * index as f32).rotate(direction))
+}
+
+/// 把速度往目標方向轉，最多轉 max_turn 弧度，速度大小不變
+fn steer_towards(velocity: Vec2, to_target: Vec2, max_turn: f32) -> Vec2 {
+    if to_target == Vec2::ZERO || velocity == Vec2::ZERO {
+        return velocity;
+    }
+    let turn = velocity.angle_to(to_target).clamp(-max_turn, max_turn);
+    Vec2::from_angle(turn).rotate(velocity)
+}
+
+/// 撞牆反彈：被擋住的軸速度反向
+fn reflect(velocity: Vec2, blocked: BVec2) -> Vec2 {
+    Vec2::new(
+        if blocked.x { -velocity.x } else { velocity.x },
+        if blocked.y { -velocity.y } else { velocity.y },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn spread_steering_and_bounces() {
+        let directions: Vec<Vec2> = spread_directions(Vec2::X, 3, FRAC_PI_2).collect();
+        assert_eq!(directions.len(), 3);
+        assert!((directions[0].angle_to(Vec2::X) - FRAC_PI_4).abs() < 1e-5);
+        assert!(directions[1].abs_diff_eq(Vec2::X, 1e-5));
+        assert!(directions[2].y > 0.0 && directions[0].y < 0.0);
+        assert!(spread_directions(Vec2::Y, 1, FRAC_PI_2).eq([Vec2::Y]));
+
+        // 轉向有上限，速度大小不變
+        let steered = steer_towards(Vec2::X * 100.0, Vec2::Y, 0.1);
+        assert!((steered.to_angle() - 0.1).abs() < 1e-5);
+        assert!((steered.length() - 100.0).abs() < 1e-3);
+        assert_eq!(steer_towards(Vec2::X, Vec2::X * 5.0, 0.1), Vec2::X);
+
+        assert_eq!(reflect(Vec2::new(3.0, -2.0), BVec2::new(true, false)), Vec2::new(-3.0, -2.0));
+        assert_eq!(reflect(Vec2::new(3.0, -2.0), BVec2::TRUE), Vec2::new(-3.0, 2.0));
+    }
+}
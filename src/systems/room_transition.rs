@@ -1,81 +1,173 @@
 use bevy::prelude::*;
 use crate::components::player::{Player, InputVector};
-use crate::components::world::{Door, RoomTile, RoomTileType};
+use crate::components::world::{Door, FloorRegion, RoomTile};
+use crate::components::enemy::Enemy;
+use crate::components::inventory::ItemPickup;
+use crate::components::camera::{CameraFollow, ScreenFade};
+use crate::resources::{RoomAssets, RoomGraph};
+use crate::systems::door_interaction::{player_in_doorway, set_door_open};
+use crate::systems::dungeon::RoomEnteredEvent;
+use crate::states::InRun;
 use crate::constants::*;
 
+/// 房間切換的進行階段
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum TransitionPhase {
+    #[default]
+    Idle,
+    /// 畫面變黑中，結束時把玩家移到相連的門
+    FadingOut { destination: Vec2, door: Entity },
+    FadingIn,
+}
+
+/// 房間切換狀態 - 淡出、移動玩家、淡入
 #[derive(Resource)]
-pub struct TransitionCooldown {
+pub struct RoomTransition {
+    pub phase: TransitionPhase,
     pub timer: Timer,
 }
 
-impl Default for TransitionCooldown {
+impl Default for RoomTransition {
     fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(0.5, TimerMode::Once),
+            phase: TransitionPhase::Idle,
+            timer: Timer::from_seconds(ROOM_TRANSITION_FADE_TIME, TimerMode::Once),
         }
     }
 }
 
-/// 房間切換系統 - 基於鍵盤輸入的簡單切換機制
-pub fn room_transition_system(
-    door_query: Query<(&Door, &Transform), Without<Player>>,
-    mut player_query: Query<(&mut Transform, &InputVector), With<Player>>,
-    mut transition_cooldown: ResMut<TransitionCooldown>,
-    time: Res<Time>,
+/// 生成全畫面的淡出遮罩（平時完全透明）
+pub fn spawn_screen_fade_system(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        ScreenFade,
+        StateScoped(InRun),
+    ));
+}
+
+/// 房間切換觸發系統 - 玩家站在開啟的門上並往走廊方向走時，開始切換到相連的門
+///
+/// 相連的門上鎖時不切換，玩家只能沿著走廊走到門前用鑰匙打開
+pub fn start_room_transition_system(
+    door_query: Query<(Entity, &Door, &Transform), Without<Player>>,
+    player_query: Query<(&Transform, &InputVector), With<Player>>,
+    mut transition: ResMut<RoomTransition>,
 ) {
-    // 更新冷卻計時器
-    transition_cooldown.timer.tick(time.delta());
-    
-    // 如果還在冷卻中，不執行傳送
-    if !transition_cooldown.timer.finished() {
+    if transition.phase != TransitionPhase::Idle {
         return;
     }
-    
-    let (mut player_transform, input_vector) = match player_query.single_mut() {
-        Ok(result) => result,
-        Err(_) => return,
+    let Ok((player_transform, input_vector)) = player_query.single() else {
+        return;
+    };
+
+    let leaving = door_query.iter().find(|(_, door, door_transform)| {
+        door.is_open
+            && door.link.is_some()
+            && player_in_doorway(player_transform, door_transform)
+            && input_vector.0.dot(door.outward.as_vec2()) > ROOM_TRANSITION_MIN_DOT
+    });
+    let Some((_, door, _)) = leaving else {
+        return;
     };
-    
-    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let trigger_distance = tile_size * 3.0; // 3個瓷磚距離作為觸發範圍
-    
-    for (door, door_transform) in &door_query {
-        let door_pos = door_transform.translation.truncate();
-        let player_pos = player_transform.translation.truncate();
-        let distance = door_pos.distance(player_pos);
-        
-        // 只有在玩家靠近門且門是開啟的情況下才檢查
-        if door.is_open && distance < trigger_distance {
-            let door_to_player = player_pos - door_pos;
-            let player_movement = input_vector.0;
-            
-            // 檢查玩家是否在向門的方向移動
-            let movement_threshold = 0.1;
-            let moving_up = player_movement.y > movement_threshold;
-            let moving_down = player_movement.y < -movement_threshold;
-            
-            // 簡化的切換邏輯：
-            // 1. 如果玩家在門下方且向上移動 -> 傳送到門上方（進入房間）
-            // 2. 如果玩家在門上方且向下移動 -> 傳送到門下方（離開房間）
-            
-            if door_to_player.y < -20.0 && moving_up {
-                // 玩家在門下方，向上移動 - 進入房間
-                let new_position = door_pos + Vec2::new(0.0, 80.0);
-                player_transform.translation.x = new_position.x;
-                player_transform.translation.y = new_position.y;
-                
-                transition_cooldown.timer.reset();
-                info!("✅ 玩家進入房間！從 {:?} 傳送到 {:?}", player_pos, new_position);
-                
-            } else if door_to_player.y > 20.0 && moving_down {
-                // 玩家在門上方，向下移動 - 離開房間
-                let new_position = door_pos + Vec2::new(0.0, -80.0);
-                player_transform.translation.x = new_position.x;
-                player_transform.translation.y = new_position.y;
-                
-                transition_cooldown.timer.reset();
-                info!("✅ 玩家離開房間！從 {:?} 傳送到 {:?}", player_pos, new_position);
+
+    let Some((linked_entity, linked_door, linked_transform)) = door_query.iter()
+        .find(|(_, other, _)| Some(other.cell) == door.link)
+    else {
+        return;
+    };
+    if linked_door.key_locked {
+        return;
+    }
+
+    // 抵達點在相連的門往房間內側兩格
+    let destination = linked_transform.translation.truncate()
+        - linked_door.outward.as_vec2() * ROOM_TRANSITION_ARRIVAL_DISTANCE;
+    transition.phase = TransitionPhase::FadingOut { destination, door: linked_entity };
+    transition.timer.reset();
+    info!("🚪 穿過門 {} 前往房間 {:?}", door.cell, linked_door.room);
+}
+
+/// 房間切換進行系統 - 更新淡出淡入遮罩，畫面全黑時移動玩家與相機並打開抵達的門
+#[allow(clippy::type_complexity)]
+pub fn update_room_transition_system(
+    mut player_query: Query<&mut Transform, With<Player>>,
+    mut camera_query: Query<&mut Transform, (With<CameraFollow>, Without<Player>)>,
+    mut door_query: Query<(&mut Door, &mut RoomTile, &mut Sprite)>,
+    mut fade_query: Query<&mut BackgroundColor, With<ScreenFade>>,
+    mut transition: ResMut<RoomTransition>,
+    room_assets: Res<RoomAssets>,
+    time: Res<Time>,
+) {
+    let alpha = match transition.phase {
+        TransitionPhase::Idle => return,
+        TransitionPhase::FadingOut { destination, door } => {
+            transition.timer.tick(time.delta());
+            if transition.timer.finished() {
+                if let Ok(mut player_transform) = player_query.single_mut() {
+                    player_transform.translation.x = destination.x;
+                    player_transform.translation.y = destination.y;
+                }
+                // 相機直接跳到玩家身上，不從舊房間平移過去
+                if let Ok(mut camera_transform) = camera_query.single_mut() {
+                    camera_transform.translation.x = destination.x;
+                    camera_transform.translation.y = destination.y;
+                }
+                if let Ok((mut door, mut room_tile, mut sprite)) = door_query.get_mut(door)
+                    && !door.is_open
+                {
+                    set_door_open(&mut door, &mut room_tile, &mut sprite, &room_assets, true);
+                }
+                transition.phase = TransitionPhase::FadingIn;
+                transition.timer.reset();
+            }
+            transition.timer.fraction()
+        }
+        TransitionPhase::FadingIn => {
+            transition.timer.tick(time.delta());
+            if transition.timer.finished() {
+                transition.phase = TransitionPhase::Idle;
             }
+            1.0 - transition.timer.fraction()
         }
+    };
+
+    if let Ok(mut background) = fade_query.single_mut() {
+        background.0 = Color::BLACK.with_alpha(alpha);
     }
-}
\ No newline at end of file
+}
+
+/// 房間顯示系統 - 進入房間後只顯示該房間、與它相連的門和走廊，以及房間裡的敵人與物品
+#[allow(clippy::type_complexity)]
+pub fn update_room_visibility_system(
+    mut entered_events: EventReader<RoomEnteredEvent>,
+    mut tile_query: Query<(&FloorRegion, &mut Visibility)>,
+    mut enemy_query: Query<(&Enemy, &mut Visibility), Without<FloorRegion>>,
+    mut pickup_query: Query<(&ItemPickup, &mut Visibility), (Without<FloorRegion>, Without<Enemy>)>,
+    graph: Res<RoomGraph>,
+) {
+    let Some(room) = entered_events.read().last().map(|event| event.room) else {
+        return;
+    };
+
+    for (region, mut visibility) in &mut tile_query {
+        let visible = match *region {
+            FloorRegion::Room(index) => index == room,
+            FloorRegion::Corridor(index) => graph.edges[index].a == room || graph.edges[index].b == room,
+        };
+        *visibility = if visible { Visibility::Inherited } else { Visibility::Hidden };
+    }
+
+    for (enemy, mut visibility) in &mut enemy_query {
+        *visibility = if enemy.room == room { Visibility::Inherited } else { Visibility::Hidden };
+    }
+
+    for (pickup, mut visibility) in &mut pickup_query {
+        *visibility = if pickup.room == room { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use std::path::Path;
+use crate::components::player::{Player, Health, CurrentWeapon, Experience};
+use crate::components::inventory::{Inventory, ItemPickup};
+use crate::components::enemy::Enemy;
+use crate::components::world::{Door, RoomTile};
+use crate::resources::{
+    SaveData, PlayerSave, PendingRestore, RoomGraph, RoomAssets, PlayerArchetype,
+    CurrentRoom, ClearedRooms, CollectedItems, DungeonSeed, ActionState, InputAction, InputBindings, PendingRebind,
+};
+use crate::systems::dungeon::{RoomClearedEvent, RoomEnteredEvent};
+use crate::systems::door_interaction::set_door_open;
+use crate::systems::state::set_run_seed;
+use crate::states::AppState;
+use crate::constants::*;
+
+/// 要求立即存檔（例如暫停選單的存檔並離開）
+#[derive(Event)]
+pub struct SaveRunEvent;
+
+/// 存檔系統 - 清除房間（檢查點）、要求存檔或關閉遊戲時寫入存檔，同一幀最多寫一次
+#[allow(clippy::too_many_arguments)]
+pub fn save_run_system(
+    mut save_events: EventReader<SaveRunEvent>,
+    mut cleared_events: EventReader<RoomClearedEvent>,
+    mut exit_events: EventReader<AppExit>,
+    player_query: Query<(&Transform, &Health, &CurrentWeapon, &Experience, &Inventory), With<Player>>,
+    door_query: Query<&Door>,
+    graph: Option<Res<RoomGraph>>,
+    seed: Res<DungeonSeed>,
+    current_room: Res<CurrentRoom>,
+    cleared_rooms: Res<ClearedRooms>,
+    collected_items: Res<CollectedItems>,
+) {
+    let requested = save_events.read().count() > 0;
+    let checkpoint = cleared_events.read().last().map(|event| event.room);
+    let exiting = exit_events.read().count() > 0;
+    if !(requested || checkpoint.is_some() || exiting) {
+        return;
+    }
+
+    let (Ok((transform, health, weapon, experience, inventory)), Some(graph)) = (player_query.single(), graph) else {
+        return;
+    };
+
+    let mut open_doors: Vec<(i32, i32)> = door_query.iter()
+        .filter(|door| door.is_open)
+        .map(|door| (door.cell.x, door.cell.y))
+        .collect();
+    open_doors.sort();
+    let mut unlocked_doors: Vec<(i32, i32)> = door_query.iter()
+        .filter(|door| !door.key_locked && graph.edges.iter().any(|edge| edge.locked && edge.door_a == door.cell))
+        .map(|door| (door.cell.x, door.cell.y))
+        .collect();
+    unlocked_doors.sort();
+    let mut cleared: Vec<usize> = cleared_rooms.0.iter().copied().collect();
+    cleared.sort();
+    let mut collected: Vec<(i32, i32)> = collected_items.0.iter().map(|cell| (cell.x, cell.y)).collect();
+    collected.sort();
+
+    let save = SaveData {
+        version: SAVE_VERSION,
+        seed: seed.0,
+        room_count: graph.rooms.len(),
+        edge_count: graph.edges.len(),
+        current_room: current_room.0,
+        player: PlayerSave {
+            position: transform.translation.truncate().into(),
+            health: health.current,
+            max_health: health.max,
+            weapon: weapon.weapon_type,
+            experience: experience.xp,
+            inventory: inventory.stacks.clone(),
+        },
+        open_doors,
+        unlocked_doors,
+        cleared_rooms: cleared,
+        collected_items: collected,
+    };
+
+    match save.write(Path::new(SAVE_FILE_PATH)) {
+        Ok(()) => match checkpoint {
+            Some(room) => info!("💾 房間 {} 已清除，已存檔到 {}", room, SAVE_FILE_PATH),
+            None => info!("💾 已存檔到 {}", SAVE_FILE_PATH),
+        },
+        Err(error) => error!("存檔失敗：{}", error),
+    }
+}
+
+/// 暫停時按下存檔並離開的按鍵就存檔並回到主選單（按鍵設定等待輸入時不處理）
+pub fn save_and_quit_system(
+    action_state: Res<ActionState>,
+    pending_rebind: Res<PendingRebind>,
+    mut save_events: EventWriter<SaveRunEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if action_state.just_pressed(InputAction::SaveAndQuit) && pending_rebind.0.is_none() {
+        save_events.write(SaveRunEvent);
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+/// 主選單：按下繼續的按鍵就讀取存檔繼續上一局；存檔損毀或版本不符時留在主選單
+pub fn continue_run_input_system(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !action_state.just_pressed(InputAction::ContinueRun) {
+        return;
+    }
+
+    match SaveData::read(Path::new(SAVE_FILE_PATH)) {
+        Ok(Some(save)) => {
+            info!("讀取存檔，種子 {}", save.seed);
+            // 以存檔的種子重新生成同一個樓層，生成後再套用存檔（只用於這一局，新的一局會另外選種子）
+            set_run_seed(&mut commands, DungeonSeed(save.seed));
+            commands.insert_resource(PendingRestore(save));
+            next_state.set(AppState::Loading);
+        }
+        Ok(None) => info!("沒有可以繼續的存檔"),
+        Err(error) => warn!("無法讀取存檔 {}：{}", SAVE_FILE_PATH, error),
+    }
+}
+
+/// 讀檔系統 - 樓層生成完成後套用存檔的玩家、門、已清除房間與已撿起的物品
+#[allow(clippy::too_many_arguments)]
+pub fn restore_run_system(
+    mut commands: Commands,
+    pending: Res<PendingRestore>,
+    graph: Res<RoomGraph>,
+    room_assets: Res<RoomAssets>,
+    archetype: Res<PlayerArchetype>,
+    mut player_query: Query<(&mut Transform, &mut Health, &mut CurrentWeapon, &mut Experience, &mut Inventory), With<Player>>,
+    mut door_query: Query<(&mut Door, &mut RoomTile, &mut Sprite)>,
+    enemy_query: Query<(Entity, &Enemy)>,
+    pickup_query: Query<(Entity, &ItemPickup)>,
+    mut current_room: ResMut<CurrentRoom>,
+    mut cleared_rooms: ResMut<ClearedRooms>,
+    mut collected_items: ResMut<CollectedItems>,
+    mut entered_events: EventWriter<RoomEnteredEvent>,
+) {
+    commands.remove_resource::<PendingRestore>();
+    let save = &pending.0;
+
+    // 樣板檔案改變時同一個種子可能生成不同的樓層，這時只保留種子重新開始
+    if save.room_count != graph.rooms.len() || save.edge_count != graph.edges.len() {
+        warn!("存檔的樓層與目前生成的樓層不一致，從起始房間重新開始");
+        return;
+    }
+
+    if let Ok((mut transform, mut health, mut weapon, mut experience, mut inventory)) = player_query.single_mut() {
+        let (x, y) = save.player.position;
+        transform.translation.x = x;
+        transform.translation.y = y;
+        health.max = save.player.max_health.max(1);
+        health.current = save.player.health.clamp(1, health.max);
+        weapon.weapon_type = save.player.weapon;
+        // 經過 add 套用堆疊上限
+        *inventory = Inventory::default();
+        for stack in &save.player.inventory {
+            inventory.add(stack.item, stack.count);
+        }
+        // 等級的能力值由 apply_level_stats_system 套用
+        *experience = Experience {
+            level: archetype.level_for_xp(save.player.experience),
+            xp: save.player.experience,
+        };
+    }
+
+    for (mut door, mut room_tile, mut sprite) in &mut door_query {
+        let cell = (door.cell.x, door.cell.y);
+        if save.unlocked_doors.contains(&cell) {
+            door.key_locked = false;
+        }
+        let open = save.open_doors.contains(&cell);
+        if door.is_open != open {
+            set_door_open(&mut door, &mut room_tile, &mut sprite, &room_assets, open);
+        }
+    }
+
+    cleared_rooms.0 = save.cleared_rooms.iter().copied().filter(|room| *room < graph.rooms.len()).collect();
+    for (entity, enemy) in &enemy_query {
+        if cleared_rooms.0.contains(&enemy.room) {
+            commands.entity(entity).despawn();
+        }
+    }
+    collected_items.0 = save.collected_items.iter().map(|&(x, y)| IVec2::new(x, y)).collect();
+    for (entity, pickup) in &pickup_query {
+        if pickup.origin.is_some_and(|cell| collected_items.0.contains(&cell)) {
+            commands.entity(entity).despawn();
+        }
+    }
+    current_room.0 = save.current_room.filter(|room| *room < graph.rooms.len());
+    if let Some(room) = current_room.0 {
+        entered_events.write(RoomEnteredEvent { room });
+    }
+
+    info!("已套用存檔：{} 個房間已清除", cleared_rooms.0.len());
+}
+
+/// 一局結束（死亡或勝利）後刪除存檔
+pub fn delete_save_system() {
+    if let Err(error) = SaveData::delete(Path::new(SAVE_FILE_PATH)) {
+        warn!("無法刪除存檔：{}", error);
+    }
+}
+
+/// 主選單是否顯示繼續遊戲的選項
+pub fn continue_hint(bindings: &InputBindings) -> Option<String> {
+    match SaveData::read(Path::new(SAVE_FILE_PATH)) {
+        Ok(Some(_)) => Some(format!("Press {} to continue", bindings.describe(InputAction::ContinueRun))),
+        Ok(None) => None,
+        Err(error) => {
+            warn!("存檔無法使用：{}", error);
+            Some("Save file is unreadable and will be overwritten".to_string())
+        }
+    }
+}
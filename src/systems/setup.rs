@@ -1,72 +1,81 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
 use crate::components::player::*;
 use crate::components::movement::Direction;
-use crate::components::camera::CameraFollow;
-use crate::components::attack::{AttackAnimation, AttackType, WeaponType};
-use crate::resources::sprites::DirectionSpriteHandles;
+use crate::components::camera::{CameraFollow, CameraEffects};
+use crate::components::attack::{PlayerFacing, Hurtbox};
+use crate::components::animation::CharacterAnimation;
+use crate::components::collision::Collider;
+use crate::components::inventory::{Inventory, ItemKind};
+use crate::components::status::StatusEffects;
+use crate::resources::{PlayerArchetype, CombatSprites, CharacterMetadata, CharacterMetadataHandles, RoomGraph, WeaponCatalog};
+use crate::systems::visual_combat::spawn_weapon;
+use crate::states::InRun;
 use crate::constants::*;
 
 pub fn setup(mut commands: Commands) {
     commands.spawn((
         Camera2d,
-        CameraFollow { speed: CAMERA_FOLLOW_SPEED },
+        CameraFollow {
+            speed: CAMERA_FOLLOW_SPEED,
+            look_ahead: CAMERA_LOOK_AHEAD,
+            dead_zone: Vec2::new(CAMERA_DEAD_ZONE.0, CAMERA_DEAD_ZONE.1),
+        },
+        CameraEffects::default(),
     ));
 }
 
-pub fn load_sprite_handles(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut handles = HashMap::new();
-    
-    // 預載所有方向的 Handle
-    handles.insert(Direction::North, asset_server.load(Direction::North.get_sprite_path()));
-    handles.insert(Direction::NorthEast, asset_server.load(Direction::NorthEast.get_sprite_path()));
-    handles.insert(Direction::East, asset_server.load(Direction::East.get_sprite_path()));
-    handles.insert(Direction::SouthEast, asset_server.load(Direction::SouthEast.get_sprite_path()));
-    handles.insert(Direction::South, asset_server.load(Direction::South.get_sprite_path()));
-    handles.insert(Direction::SouthWest, asset_server.load(Direction::SouthWest.get_sprite_path()));
-    handles.insert(Direction::West, asset_server.load(Direction::West.get_sprite_path()));
-    handles.insert(Direction::NorthWest, asset_server.load(Direction::NorthWest.get_sprite_path()));
-    
-    commands.insert_resource(DirectionSpriteHandles { handles });
-    info!("角色方向 Sprite 已預載完成");
-}
+/// 在起始房間生成玩家，能力值、外觀與起始武器都來自 PlayerArchetype
+pub fn spawn_player(
+    mut commands: Commands,
+    archetype: Res<PlayerArchetype>,
+    weapons: Res<WeaponCatalog>,
+    sprites: Res<CombatSprites>,
+    characters: Res<CharacterMetadataHandles>,
+    metadata: Res<Assets<CharacterMetadata>>,
+    graph: Res<RoomGraph>,
+) {
+    // 生成在起始房間中央
+    let spawn_position = graph.room_center(graph.start_room);
 
-pub fn spawn_player(mut commands: Commands, sprite_handles: Res<DirectionSpriteHandles>) {
-    let initial_direction = Direction::South;
-    let sprite_handle = sprite_handles.handles.get(&initial_direction)
-        .expect("初始方向的 Sprite Handle 應該已預載").clone();
-        
-    commands.spawn((
+    // 1 級角色的圖集 Sprite，縮小到設定的大小
+    let stats = archetype.level(1);
+    let character = characters.get(stats.character).cloned().unwrap_or_default();
+    let mut sprite = metadata.get(&character)
+        .map(|character| character.sprite(Direction::East))
+        .unwrap_or_default();
+    sprite.custom_size = Some(Vec2::splat(archetype.sprite_size));
+
+    let player_entity = commands.spawn((
+        // 玩家 Sprite 與動畫狀態機
+        (sprite, CharacterAnimation::new(character)),
+        Transform::from_translation(spawn_position.extend(Z_LAYER_PLAYER))
+            .with_scale(Vec3::splat(archetype.scale)),
         Player,
-        Sprite::from_image(sprite_handle),
-        Transform::from_translation(Vec3::new(0.0, -400.0, Z_LAYER_PLAYER))
-            .with_scale(Vec3::splat(PLAYER_SCALE)),
-        Health {
-            current: PLAYER_MAX_HEALTH,
-            max: PLAYER_MAX_HEALTH,
-        },
-        Velocity(Vec2::ZERO),
-        InputVector(Vec2::ZERO),
-        Speed(PLAYER_SPEED),
-        initial_direction,
-        // 攻擊相關 Components
-        AttackCooldown {
-            timer: {
-                let mut timer = Timer::from_seconds(0.5, TimerMode::Once);
-                timer.tick(std::time::Duration::from_secs_f32(0.5)); // 設為已完成狀態
-                timer
-            },
-        },
-        AttackDamage(10), // 基礎攻擊傷害
-        AttackRange(100.0), // 攻擊範圍
-        AttackAnimation {
-            timer: Timer::from_seconds(0.2, TimerMode::Once), // 攻擊動畫持續0.2秒
-            is_attacking: false,
-        },
-        AttackType::Melee, // 近戰攻擊（揮劍）
-        CurrentWeapon {
-            weapon_type: WeaponType::Sword, // 默認武器是劍
-        },
-    ));
-    info!("騎士英雄已生成（3倍放大）！");
-}
\ No newline at end of file
+        PlayerFacing { direction: Vec2::X }, // 默認面向右
+        // 移動
+        (InputVector(Vec2::ZERO), Velocity(Vec2::ZERO), Speed(stats.speed)),
+        Health { current: stats.max_health, max: stats.max_health },
+        AttackDamage(stats.attack_damage),
+        StatusEffects::default(),
+        // 冷卻長度依武器設定，一開始就可以攻擊
+        (
+            AttackCooldown { timer: Timer::from_seconds(0.0, TimerMode::Once) },
+            DashCooldown { timer: Timer::from_seconds(0.0, TimerMode::Once) },
+        ),
+        CurrentWeapon { weapon_type: archetype.starting_weapon },
+        (starting_inventory(&archetype), Experience::default()),
+        Hurtbox { radius: archetype.hurtbox_radius },
+        Collider { half_size: Vec2::splat(archetype.collider_half_size) },
+        StateScoped(InRun),
+    )).id();
+
+    spawn_weapon(&mut commands, player_entity, archetype.starting_weapon, &weapons, &sprites);
+    info!("玩家 {} 已生成！", stats.character);
+}
+
+/// 起始物品欄只有起始武器
+fn starting_inventory(archetype: &PlayerArchetype) -> Inventory {
+    let mut inventory = Inventory::default();
+    inventory.add(ItemKind::Weapon(archetype.starting_weapon), 1);
+    inventory
+}
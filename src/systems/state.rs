@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use crate::components::enemy::Enemy;
+use crate::resources::{
+    RoomGraph, CurrentRoom, ClearedRooms, CollectedItems, DungeonSeed, PinnedSeed, GameRng, RngStream,
+    ActionState, InputAction, InputBindings, PendingRebind,
+};
+use crate::systems::save::continue_hint;
+use crate::systems::room_transition::RoomTransition;
+use crate::states::AppState;
+use crate::constants::*;
+
+/// 回到主選單時重設這一局的資源（實體由 StateScoped 自動清除）
+pub fn reset_run_system(mut commands: Commands) {
+    commands.remove_resource::<RoomGraph>();
+    commands.insert_resource(CurrentRoom::default());
+    commands.insert_resource(ClearedRooms::default());
+    commands.insert_resource(CollectedItems::default());
+    commands.insert_resource(RoomTransition::default());
+}
+
+/// 設定這一局的種子，並由它重建遊戲進行中的亂數產生器
+pub fn set_run_seed(commands: &mut Commands, seed: DungeonSeed) {
+    commands.insert_resource(seed);
+    commands.insert_resource(GameRng(seed.rng(RngStream::Runtime)));
+}
+
+pub fn spawn_main_menu_system(mut commands: Commands, bindings: Res<InputBindings>) {
+    let hint = match continue_hint(&bindings) {
+        Some(continue_hint) => format!("Press Enter to start\n{continue_hint}"),
+        None => "Press Enter to start".to_string(),
+    };
+    spawn_screen(
+        &mut commands,
+        AppState::MainMenu,
+        "Dungeon of Rust",
+        &hint,
+        Color::srgb(MENU_BACKGROUND_COLOR.0, MENU_BACKGROUND_COLOR.1, MENU_BACKGROUND_COLOR.2),
+    );
+}
+
+pub fn spawn_pause_screen_system(mut commands: Commands, bindings: Res<InputBindings>) {
+    let hint = format!(
+        "Press {} to resume\nPress {} to save and quit",
+        bindings.describe(InputAction::Pause),
+        bindings.describe(InputAction::SaveAndQuit),
+    );
+    spawn_screen(&mut commands, AppState::Paused, "Paused", &hint, overlay_color());
+}
+
+pub fn spawn_game_over_screen_system(mut commands: Commands) {
+    spawn_screen(&mut commands, AppState::GameOver, "You Died", "Press Enter to return to the menu", overlay_color());
+}
+
+pub fn spawn_victory_screen_system(mut commands: Commands) {
+    spawn_screen(&mut commands, AppState::Victory, "Victory!", "Press Enter to return to the menu", overlay_color());
+}
+
+fn overlay_color() -> Color {
+    Color::srgba(0.0, 0.0, 0.0, OVERLAY_ALPHA)
+}
+
+/// 生成置中的全螢幕標題畫面，離開指定狀態時自動清除；回傳提示文字的實體
+pub fn spawn_screen(commands: &mut Commands, state: AppState, title: &str, hint: &str, background: Color) -> Entity {
+    let mut hint_entity = Entity::PLACEHOLDER;
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(24.0),
+            ..default()
+        },
+        BackgroundColor(background),
+        StateScoped(state),
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new(title),
+            TextFont { font_size: UI_TITLE_FONT_SIZE, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        hint_entity = parent.spawn((
+            Text::new(hint),
+            TextFont { font_size: UI_HINT_FONT_SIZE, ..default() },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        )).id();
+    });
+    hint_entity
+}
+
+/// 主選單：按 Enter、空白鍵或手把確認鍵開始新的一局（沒有固定種子時使用新的隨機種子）
+pub fn main_menu_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    pinned_seed: Res<PinnedSeed>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::Space]) || gamepad_confirm(&gamepads) {
+        let seed = pinned_seed.next_run_seed();
+        info!("開始新的一局，種子 {}", seed.0);
+        set_run_seed(&mut commands, seed);
+        next_state.set(AppState::Loading);
+    }
+}
+
+/// 樓層生成完成後開始遊戲
+pub fn finish_loading_system(mut next_state: ResMut<NextState<AppState>>) {
+    info!("樓層載入完成，開始遊戲");
+    next_state.set(AppState::Playing);
+}
+
+/// 暫停動作切換暫停（按鍵設定等待輸入時不切換）
+pub fn toggle_pause_system(
+    action_state: Res<ActionState>,
+    pending_rebind: Res<PendingRebind>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !action_state.just_pressed(InputAction::Pause) || pending_rebind.0.is_some() {
+        return;
+    }
+
+    match state.get() {
+        AppState::Playing => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::Playing),
+        _ => {}
+    }
+}
+
+/// 樓層上的敵人全部被擊倒就獲勝
+pub fn victory_check_system(
+    enemy_query: Query<(), With<Enemy>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if enemy_query.is_empty() {
+        info!("所有敵人都被擊倒了！");
+        next_state.set(AppState::Victory);
+    }
+}
+
+/// 結束畫面：按 Enter 或手把確認鍵回到主選單
+pub fn end_screen_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Enter) || gamepad_confirm(&gamepads) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+/// 任一手把按下 South（A）或 Start
+fn gamepad_confirm(gamepads: &Query<&Gamepad>) -> bool {
+    gamepads.iter().any(|gamepad| gamepad.any_just_pressed([GamepadButton::South, GamepadButton::Start]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn start_new_run(world: &mut World) -> DungeonSeed {
+        world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Enter);
+        world.run_system_once(main_menu_input_system).unwrap();
+        *world.resource::<DungeonSeed>()
+    }
+
+    #[test]
+    fn each_new_run_gets_a_fresh_seed_unless_one_is_pinned() {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<NextState<AppState>>();
+        world.init_resource::<PinnedSeed>();
+        assert_ne!(start_new_run(&mut world), start_new_run(&mut world));
+
+        world.insert_resource(PinnedSeed(Some(DungeonSeed(42))));
+        assert_eq!(start_new_run(&mut world), DungeonSeed(42));
+        assert_eq!(start_new_run(&mut world), DungeonSeed(42));
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use crate::components::player::Health;
+use crate::components::attack::{DamageEvent, DamageSource};
+use crate::components::status::{StatusEffects, ApplyStatusEvent};
+
+/// 附加狀態效果系統 - 依免疫規則附加或疊加效果
+pub fn apply_status_system(
+    mut status_events: EventReader<ApplyStatusEvent>,
+    mut query: Query<&mut StatusEffects>,
+) {
+    for event in status_events.read() {
+        let Ok(mut effects) = query.get_mut(event.target) else {
+            continue; // 目標可能已被移除，或不會受到狀態效果
+        };
+
+        if effects.apply(event.kind) {
+            debug!("{:?} 受到 {:?}", event.target, event.kind);
+        } else {
+            debug!("{:?} 免疫 {:?}", event.target, event.kind);
+        }
+    }
+}
+
+/// 狀態效果計時系統 - 持續傷害走一般的傷害事件，回復直接加到生命上，時間到就移除效果
+pub fn update_status_effects_system(
+    mut query: Query<(Entity, &mut StatusEffects, &mut Health)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut effects, mut health) in &mut query {
+        if effects.effects.is_empty() && effects.cooldowns.is_empty() {
+            continue;
+        }
+
+        let health_change = effects.tick(time.delta());
+        if health_change < 0 {
+            // 持續傷害沒有攻擊者也沒有擊退，標記為狀態效果，不觸發命中的打擊感
+            damage_events.write(DamageEvent {
+                attacker: entity,
+                target: entity,
+                amount: -health_change,
+                knockback: Vec2::ZERO,
+                source: DamageSource::Status,
+            });
+        } else if health_change > 0 && health.current > 0 {
+            health.current = (health.current + health_change).min(health.max);
+        }
+    }
+}
+
+/// 狀態效果顏色系統 - 效果期間把角色圖片染成效果的顏色，結束後恢復
+pub fn status_tint_system(
+    mut query: Query<(&StatusEffects, &mut Sprite), Changed<StatusEffects>>,
+) {
+    for (effects, mut sprite) in &mut query {
+        let tint = effects.tint();
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+}
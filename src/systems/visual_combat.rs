@@ -1,110 +1,169 @@
 use bevy::prelude::*;
 use std::f32::consts::PI;
+use std::collections::HashSet;
 use crate::components::{
-    player::{Player, InputVector, Speed},
-    attack::{PlayerFacing, Weapon, WeaponOffset, WeaponSwingAnimation, WeaponType, WeaponSprites},
+    player::{Player, AttackCooldown, AttackDamage, CurrentWeapon},
+    attack::{PlayerFacing, Weapon, WeaponOffset, WeaponSwingAnimation, WeaponType, WeaponSprites, Hitbox},
+    status::StatusEffects,
 };
+use crate::components::animation::CharacterAnimation;
+use crate::components::inventory::{Inventory, ItemKind};
+use crate::resources::{CombatSprites, CharacterMetadata, WeaponCatalog, WeaponAttack, ActionState, InputAction};
+use crate::systems::projectile::spawn_projectiles;
+use crate::constants::*;
 
-// 系統：處理輸入
-pub fn player_input_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut InputVector, With<Player>>,
+// 系統：根據面向與角色動畫影格的手部關鍵點更新武器偏移
+#[allow(clippy::type_complexity)]
+pub fn update_weapon_offset_system(
+    player_query: Query<(&PlayerFacing, &CharacterAnimation, &Sprite), (With<Player>, Without<Weapon>)>,
+    mut weapon_query: Query<(&mut WeaponOffset, &mut Transform, &mut Sprite, &WeaponSprites, Option<&WeaponSwingAnimation>), With<Weapon>>,
+    metadata: Res<Assets<CharacterMetadata>>,
 ) {
-    if let Ok(mut input_vector) = query.single_mut() {
-        let mut direction = Vec2::ZERO;
-        
-        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
-            direction.y += 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
-            direction.y -= 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
-            direction.x -= 1.0;
+    let Ok((facing, animation, player_sprite)) = player_query.single() else {
+        return;
+    };
+    let character = metadata.get(&animation.metadata);
+
+    for (mut offset, mut transform, mut sprite, weapon_sprites, swing) in &mut weapon_query {
+        // 根據面向計算武器角度
+        let (angle, is_left_side) = calculate_weapon_rotation(&facing.direction);
+
+        // 手的位置來自目前方向與影格的關鍵點，沒有關鍵點時拿在身體中央
+        let hand = character
+            .and_then(|character| hand_anchor(character, animation, player_sprite, &offset.hand))
+            .unwrap_or(Vec2::ZERO);
+        let position = hand + facing.direction * offset.grip;
+
+        // 直立拿著的武器不跟著面向旋轉
+        let angle = if offset.aim { angle } else { 0.0 };
+        offset.position = position;
+        offset.base_angle = angle;
+
+        // 根據面向切換圖片：有左側圖片就換圖，否則翻轉（朝面向旋轉的上下翻，直立的左右翻）
+        let (image, flipped) = match (&weapon_sprites.left_sprite, is_left_side) {
+            (Some(left), true) => (left, false),
+            (None, true) => (&weapon_sprites.right_sprite, true),
+            (_, false) => (&weapon_sprites.right_sprite, false),
+        };
+        if sprite.image != *image {
+            sprite.image = image.clone();
         }
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
-            direction.x += 1.0;
+        sprite.flip_x = flipped && !offset.aim;
+        sprite.flip_y = flipped && offset.aim;
+
+        transform.translation = position.extend(offset.z_layer);
+        // 揮擊中由揮擊動畫控制旋轉
+        if !swing.is_some_and(|swing| swing.is_attacking) {
+            transform.rotation = Quat::from_rotation_z(angle);
         }
-        
-        input_vector.0 = direction.normalize_or_zero();
     }
 }
 
-// 系統：更新玩家面向（記錄最後移動方向）
-pub fn update_player_facing_system(
-    mut player_query: Query<(&mut PlayerFacing, &InputVector), With<Player>>,
-) {
-    for (mut facing, input) in &mut player_query {
-        // 如果有移動輸入，更新面向
-        if input.0.length() > 0.1 {
-            facing.direction = input.0.normalize();
-        }
-    }
+// 輔助函數：角色目前影格的手部關鍵點，換算成玩家本地座標
+fn hand_anchor(character: &CharacterMetadata, animation: &CharacterAnimation, sprite: &Sprite, label: &str) -> Option<Vec2> {
+    let point = character.keypoint(animation.kind, animation.direction, animation.frame, label)?;
+    // 圖片被縮放到 custom_size 時關鍵點跟著縮放
+    let scale = sprite.custom_size.map_or(Vec2::ONE, |size| size / character.size.as_vec2());
+    Some(point * scale)
 }
 
-// 系統：移動玩家
-pub fn player_movement_system(
-    mut query: Query<(&mut Transform, &InputVector, &Speed), With<Player>>,
+// 系統：處理攻擊輸入 - 冷卻結束後開始揮擊，遠程武器同時發射投射物；暈眩時不能攻擊
+#[allow(clippy::type_complexity)]
+pub fn visual_attack_input_system(
+    mut commands: Commands,
+    mut attack_events: EventReader<crate::systems::input::AttackInputEvent>,
+    mut player_query: Query<(Entity, &Transform, &PlayerFacing, &AttackDamage, &mut AttackCooldown, &StatusEffects), With<Player>>,
+    mut weapon_query: Query<(&Weapon, &mut WeaponSwingAnimation, &Transform), Without<Player>>,
+    catalog: Res<WeaponCatalog>,
     time: Res<Time>,
 ) {
-    for (mut transform, input, speed) in &mut query {
-        if input.0.length() > 0.1 {
-            let movement = input.0 * speed.0 * time.delta_secs();
-            transform.translation += movement.extend(0.0);
+    let Ok((player_entity, player_transform, facing, damage, mut cooldown, effects)) = player_query.single_mut() else {
+        return;
+    };
+    cooldown.timer.tick(time.delta());
+    if effects.is_stunned() {
+        attack_events.clear();
+        return;
+    }
+
+    for _event in attack_events.read() {
+        for (weapon, mut swing_animation, weapon_transform) in &mut weapon_query {
+            if swing_animation.is_attacking || !cooldown.timer.finished() {
+                continue;
+            }
+
+            // 開始攻擊動畫
+            let definition = catalog.get(weapon.weapon_type);
+            swing_animation.is_attacking = true;
+            swing_animation.timer.reset();
+            cooldown.timer = Timer::from_seconds(definition.cooldown, TimerMode::Once);
+
+            if matches!(definition.attack, WeaponAttack::Projectile(_)) {
+                // 從武器目前的世界座標朝面向發射
+                let origin = player_transform.mul_transform(*weapon_transform).translation.truncate();
+                let damage = (damage.0 as f32 * definition.damage_multiplier * effects.damage_multiplier()).round() as i32;
+                spawn_projectiles(&mut commands, player_entity, origin, facing.direction, damage, weapon.weapon_type, definition);
+            }
+
+            info!("{:?} 攻擊！", weapon.weapon_type);
         }
     }
 }
 
-// 系統：根據面向更新武器偏移
-pub fn update_weapon_offset_system(
-    player_query: Query<&PlayerFacing, (With<Player>, Changed<PlayerFacing>)>,
-    mut weapon_query: Query<(&mut WeaponOffset, &mut Transform, &mut Sprite, &WeaponSprites), With<Weapon>>,
+// 系統：按切換武器鍵時換成物品欄中的下一把武器（揮擊中不能切換）
+pub fn switch_weapon_system(
+    action_state: Res<ActionState>,
+    mut player_query: Query<(&mut CurrentWeapon, &Inventory), With<Player>>,
+    weapon_query: Query<&WeaponSwingAnimation, With<Weapon>>,
 ) {
-    if let Ok(facing) = player_query.single() {
-        for (mut offset, mut transform, mut sprite, weapon_sprites) in &mut weapon_query {
-            // 根據面向計算武器位置和角度
-            let (position, angle, z_layer, is_left_side) = calculate_weapon_position_and_rotation(&facing.direction);
-            
-            offset.position = position;
-            offset.base_angle = angle;
-            offset.z_layer = z_layer;
-            
-            // 根據面向切換圖片
-            sprite.image = if is_left_side {
-                weapon_sprites.left_sprite.clone()
-            } else {
-                weapon_sprites.right_sprite.clone()
-            };
-            
-            // 移除翻轉，因為我們用不同圖片
-            sprite.flip_x = false;
-            sprite.flip_y = false;
-            
-            // 立即應用到 Transform（如果沒有攻擊動畫）
-            transform.translation = position.extend(z_layer);
-            transform.rotation = Quat::from_rotation_z(angle);
+    if !action_state.just_pressed(InputAction::SwitchWeapon) {
+        return;
+    }
+    let Ok((mut current, inventory)) = player_query.single_mut() else {
+        return;
+    };
+    if weapon_query.iter().any(|swing| swing.is_attacking) {
+        return;
+    }
+
+    match next_weapon(current.weapon_type, inventory) {
+        Some(weapon) => {
+            current.weapon_type = weapon;
+            info!("切換到 {}", ItemKind::Weapon(weapon).label());
         }
+        None => info!("沒有其他武器可以切換"),
     }
 }
 
-// 系統：處理攻擊輸入和動畫觸發
-pub fn visual_attack_input_system(
-    mut attack_events: EventReader<crate::systems::input::AttackInputEvent>,
-    mut weapon_query: Query<&mut WeaponSwingAnimation, With<Weapon>>,
+// 系統：目前武器改變（切換或讀檔）時換掉武器子實體的圖片與攻擊方式
+#[allow(clippy::type_complexity)]
+pub fn equip_current_weapon_system(
+    mut commands: Commands,
+    player_query: Query<(Entity, &CurrentWeapon, &Children), (With<Player>, Changed<CurrentWeapon>)>,
+    weapon_query: Query<&Weapon>,
+    catalog: Res<WeaponCatalog>,
+    sprites: Res<CombatSprites>,
 ) {
-    for _event in attack_events.read() {
-        for mut swing_animation in &mut weapon_query {
-            if !swing_animation.is_attacking {
-                // 開始攻擊動畫
-                swing_animation.is_attacking = true;
-                swing_animation.timer.reset();
-                
-                info!("開始武器揮擊動畫！");
+    for (player_entity, current, children) in &player_query {
+        for child in children.iter() {
+            let Ok(weapon) = weapon_query.get(child) else {
+                continue;
+            };
+            if weapon.weapon_type != current.weapon_type {
+                equip_weapon(&mut commands, child, player_entity, current.weapon_type, &catalog, &sprites);
             }
         }
     }
 }
 
+// 輔助函數：物品欄中排在目前武器之後的下一把武器（依 WeaponType::ALL 的順序循環）
+fn next_weapon(current: WeaponType, inventory: &Inventory) -> Option<WeaponType> {
+    let index = WeaponType::ALL.iter().position(|weapon| *weapon == current).unwrap_or(0);
+    (1..WeaponType::ALL.len())
+        .map(|step| WeaponType::ALL[(index + step) % WeaponType::ALL.len()])
+        .find(|weapon| inventory.count(ItemKind::Weapon(*weapon)) > 0)
+}
+
 // 系統：更新武器揮擊動畫
 pub fn update_weapon_swing_animation_system(
     mut weapon_query: Query<(&mut WeaponSwingAnimation, &mut Transform, &WeaponOffset), With<Weapon>>,
@@ -120,7 +179,7 @@ pub fn update_weapon_swing_animation_system(
                 let progress = swing.timer.elapsed_secs() / swing.timer.duration().as_secs_f32();
                 
                 // 獲取當前面向狀態
-                let (_, _, _, is_left_side) = calculate_weapon_position_and_rotation(&facing.direction);
+                let (_, is_left_side) = calculate_weapon_rotation(&facing.direction);
                 
                 if swing.timer.finished() {
                     // 動畫結束
@@ -132,12 +191,10 @@ pub fn update_weapon_swing_animation_system(
                     // 插值計算當前角度
                     let current_angle = lerp_angle(swing.from_angle, swing.to_angle, progress);
                     
-                    // 根據左右側調整攻擊角度
-                    let adjusted_angle = if is_left_side {
-                        // 左側需要相對於基礎角度來計算攻擊角度
-                        offset.base_angle + current_angle
+                    // 直立拿著的武器在左側時圖片左右翻轉，揮擊方向也跟著反過來
+                    let adjusted_angle = if is_left_side && !offset.aim {
+                        offset.base_angle - current_angle
                     } else {
-                        // 右側正常計算
                         offset.base_angle + current_angle
                     };
                     
@@ -148,34 +205,15 @@ pub fn update_weapon_swing_animation_system(
     }
 }
 
-// 輔助函數：根據面向方向計算武器位置和旋轉
-fn calculate_weapon_position_and_rotation(facing_direction: &Vec2) -> (Vec2, f32, f32, bool) {
+// 輔助函數：根據面向方向計算武器旋轉，以及是否使用左側圖片
+fn calculate_weapon_rotation(facing_direction: &Vec2) -> (f32, bool) {
     let angle = facing_direction.y.atan2(facing_direction.x);
     let octant = get_direction_octant(angle);
-    
-    // 根據面向調整武器位置偏移（往上調整3個像素單位）
-    let hand_offset = match octant {
-        0 => Vec2::new(8.0, 1.0),     // 向右 (從-2.0調到1.0)
-        1 => Vec2::new(6.0, 7.0),     // 右上 (從4.0調到7.0)
-        2 => Vec2::new(-2.0, 11.0),   // 向上 (從8.0調到11.0)
-        3 => Vec2::new(-6.0, 7.0),    // 左上 (從4.0調到7.0)
-        4 => Vec2::new(-8.0, 1.0),    // 向左 (從-2.0調到1.0)
-        5 => Vec2::new(-6.0, -3.0),   // 左下 (從-6.0調到-3.0)
-        6 => Vec2::new(-2.0, -5.0),   // 向下 (從-8.0調到-5.0)
-        7 => Vec2::new(6.0, -3.0),    // 右下 (從-6.0調到-3.0)
-        _ => Vec2::new(8.0, 1.0),     // 默認向右
-    };
-    
-    // 武器基礎角度
-    let weapon_angle = angle;
-    
-    // Z 層級：所有方向都在前景，讓武器始終可見
-    let z_layer = 1.0;
-    
+
     // 判斷是否為左側方向（需要使用左側圖片）
     let is_left_side = matches!(octant, 3 | 4 | 5);
-    
-    (hand_offset, weapon_angle, z_layer, is_left_side)
+
+    (angle, is_left_side)
 }
 
 // 輔助函數：將角度轉換為8方向
@@ -190,63 +228,97 @@ fn lerp_angle(from: f32, to: f32, t: f32) -> f32 {
     from + diff * t
 }
 
-// 系統：生成玩家和武器的階層結構
-pub fn spawn_player_with_weapon_system(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
+// 生成武器並掛到玩家底下（子實體）
+pub fn spawn_weapon(
+    commands: &mut Commands,
+    player_entity: Entity,
+    weapon_type: WeaponType,
+    catalog: &WeaponCatalog,
+    sprites: &CombatSprites,
 ) {
-    // 生成玩家（父實體）
-    let player_entity = commands.spawn((
-        // 玩家 Sprite
-        Sprite::from_image(asset_server.load("sprites/characters/knight_lv1.png")),
-        Transform::from_translation(Vec3::new(0.0, 300.0, 0.0))  // 生成在房間外面（更北邊）
-            .with_scale(Vec3::splat(4.0)), // 增大角色
-        Player,
-        PlayerFacing { direction: Vec2::X }, // 默認面向右
-        crate::components::player::InputVector(Vec2::ZERO),
-        crate::components::player::Health { current: 100, max: 100 },
-        crate::components::player::Speed(200.0),
-        crate::components::player::AttackDamage(25),
-        crate::components::player::AttackCooldown { 
-            timer: Timer::from_seconds(0.5, TimerMode::Once) 
-        },
-        crate::components::player::CurrentWeapon { weapon_type: WeaponType::Sword },
-        crate::components::attack::AttackAnimation {
-            timer: Timer::from_seconds(0.3, TimerMode::Once),
-            is_attacking: false,
-        },
-    )).id();
-    
-    // 載入武器圖片資源
-    let right_sprite_handle = asset_server.load("sprites/weapons/sword.png");
-    let left_sprite_handle = asset_server.load("sprites/weapons/sword_left.png");
-    
-    // 生成武器（子實體）
     let weapon_entity = commands.spawn((
+        Sprite::default(),
+        // 位置由 update_weapon_offset_system 依手部關鍵點更新
+        Transform::default(),
+    )).id();
+    equip_weapon(commands, weapon_entity, player_entity, weapon_type, catalog, sprites);
+
+    // 建立父子關係
+    commands.entity(player_entity).add_child(weapon_entity);
+}
+
+// 依武器設定表設定武器實體的圖片、揮擊與攻擊方式（生成與切換武器共用）
+fn equip_weapon(
+    commands: &mut Commands,
+    weapon_entity: Entity,
+    player_entity: Entity,
+    weapon_type: WeaponType,
+    catalog: &WeaponCatalog,
+    sprites: &CombatSprites,
+) {
+    let definition = catalog.get(weapon_type);
+    let weapon_sprites = sprites.weapon(weapon_type);
+    let mut entity = commands.entity(weapon_entity);
+
+    entity.insert((
         // 武器 Sprite（默認使用右側圖片）
-        Sprite::from_image(right_sprite_handle.clone()),
-        Transform::from_translation(Vec3::new(8.0, 1.0, 1.0))  // 更新初始位置往上移
-            .with_scale(Vec3::splat(0.8)), // 因為會繼承父物件4x縮放，所以用0.8相當於3.2x
-        Weapon { weapon_type: WeaponType::Sword },
-        WeaponSprites {
-            right_sprite: right_sprite_handle,
-            left_sprite: left_sprite_handle,
-        },
+        Sprite::from_image(weapon_sprites.right_sprite.clone()),
+        // 會繼承父物件 4x 縮放，例如劍用 0.8 相當於 3.2x
+        Transform::from_translation(Vec3::new(definition.grip, 0.0, 1.0))
+            .with_scale(Vec3::splat(definition.scale)),
+        Weapon { weapon_type },
+        weapon_sprites,
         WeaponOffset {
-            position: Vec2::new(8.0, 1.0),   // 更新位置以匹配初始位置
+            position: Vec2::new(definition.grip, 0.0),
             base_angle: 0.0,
-            z_layer: 1.0,
+            z_layer: 1.0,  // 所有方向都在前景，讓武器始終可見
+            hand: PLAYER_WEAPON_HAND.to_string(),
+            grip: definition.grip,
+            aim: definition.aim,
         },
         WeaponSwingAnimation {
-            timer: Timer::from_seconds(0.5, TimerMode::Once), // 0.5秒揮擊動畫
-            from_angle: -PI / 4.0,  // 起始角度 -45度
-            to_angle: PI / 4.0,     // 結束角度 +45度  
+            timer: Timer::from_seconds(definition.swing_duration, TimerMode::Once),
+            from_angle: definition.swing_arc.0,
+            to_angle: definition.swing_arc.1,
             is_attacking: false,
         },
-    )).id();
-    
-    // 建立父子關係
-    commands.entity(player_entity).add_child(weapon_entity);
-    
-    info!("玩家和武器階層已生成！");
+    ));
+
+    // 只有近戰武器本身帶判定框；遠程武器的判定框在投射物上
+    match definition.attack {
+        WeaponAttack::Melee { reach, radius, knockback } => {
+            entity.insert(Hitbox {
+                owner: player_entity,
+                reach,
+                radius,
+                knockback,
+                damage_multiplier: definition.damage_multiplier,
+                status: definition.on_hit,
+                hit_entities: HashSet::new(),
+            });
+        }
+        WeaponAttack::Projectile(_) => {
+            entity.remove::<Hitbox>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_cycles_through_owned_weapons_only() {
+        let mut inventory = Inventory::default();
+        inventory.add(ItemKind::Weapon(WeaponType::Sword), 1);
+        assert_eq!(next_weapon(WeaponType::Sword, &inventory), None);
+
+        inventory.add(ItemKind::Weapon(WeaponType::Arrow), 1);
+        assert_eq!(next_weapon(WeaponType::Sword, &inventory), Some(WeaponType::Arrow));
+        assert_eq!(next_weapon(WeaponType::Arrow, &inventory), Some(WeaponType::Sword));
+
+        inventory.add(ItemKind::Weapon(WeaponType::Magic), 1);
+        assert_eq!(next_weapon(WeaponType::Sword, &inventory), Some(WeaponType::Magic));
+        assert_eq!(next_weapon(WeaponType::Magic, &inventory), Some(WeaponType::Arrow));
+    }
 }
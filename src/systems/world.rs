@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 use rand::Rng;
-use crate::components::world::{GridTile, RoomTile, Room, RoomTileType, CompoundRoom, CompoundRoomType, RoomRect, Door};
+use std::collections::HashMap;
+use crate::components::world::{GridTile, RoomTile, RoomTileType, CompoundRoom, CompoundRoomType, RoomRect, Door, FloorRegion, TileGrid};
+use crate::components::interaction::Interactable;
+use crate::systems::door_interaction::door_prompt;
 use crate::resources::RoomAssets;
+use crate::states::InRun;
 use crate::constants::*;
 
 pub fn spawn_grid(mut commands: Commands) {
@@ -32,200 +36,131 @@ pub fn spawn_grid(mut commands: Commands) {
     info!("格子地板已生成（20x20）");
 }
 
-pub fn spawn_room(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-) {
-    // 初始化房間資源
-    let room_assets = RoomAssets::load_all(&asset_server);
-    commands.insert_resource(room_assets);
-    
-    // 隨機選擇房間類型
-    let mut rng = rand::thread_rng();
-    let room_type_choice = rng.gen_range(0..4);
-    
-    match room_type_choice {
+/// 隨機產生一個房間樣板（以原點為中心），供樓層生成器平移後放置
+pub fn generate_room_template(rng: &mut impl Rng) -> CompoundRoom {
+    match rng.gen_range(0..4) {
         0 => {
-            // 基本矩形房間 (30% 機率)
+            // 基本矩形房間
             let room_width = rng.gen_range(8..15);
             let room_height = rng.gen_range(6..10);
-            let room_x = -(room_width as i32) / 2;
-            let room_y = -(room_height as i32) / 2;
-            
-            generate_room_tiles(&mut commands, &asset_server, room_width, room_height, room_x, room_y, true);
-            info!("矩形房間已生成 ({}x{})", room_width, room_height);
-        },
-        1 => {
-            // L 形房間 (25% 機率)
-            let compound_room = generate_l_shape_room(&mut rng);
-            spawn_compound_room(&mut commands, &asset_server, compound_room);
-            info!("L 形房間已生成");
-        },
-        2 => {
-            // T 形房間 (25% 機率)
-            let compound_room = generate_t_shape_room(&mut rng);
-            spawn_compound_room(&mut commands, &asset_server, compound_room);
-            info!("T 形房間已生成");
+            CompoundRoom {
+                rectangles: vec![RoomRect {
+                    x: -(room_width as i32) / 2,
+                    y: -(room_height as i32) / 2,
+                    width: room_width,
+                    height: room_height,
+                }],
+                room_type: CompoundRoomType::Rectangle,
+            }
         },
-        _ => {
-            // 十字形房間 (20% 機率)
-            let compound_room = generate_plus_shape_room(&mut rng);
-            spawn_compound_room(&mut commands, &asset_server, compound_room);
-            info!("十字形房間已生成");
-        }
+        1 => generate_l_shape_room(rng),
+        2 => generate_t_shape_room(rng),
+        _ => generate_plus_shape_room(rng),
     }
 }
 
+/// 在瓷磚格上放置一個矩形房間的牆壁、地板與門
 fn generate_room_tiles(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
+    grid: &mut TileGrid,
     width: usize,
     height: usize,
     start_x: i32,
     start_y: i32,
-    should_generate_door: bool,
+    doors: &[IVec2],
 ) {
-    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;  // 使用房間瓷磚專用尺寸並考慮縮放
-    let room_assets = RoomAssets::load_all(asset_server);
-    
     // 生成房間結構：包含南牆外側 + 內部 + 北牆
     // 南牆外側（y=0）-> 南牆內側（y=1）-> 地板（y=2..height-1）-> 北牆（y=height-1）
     let total_height = height + 1; // 增加一行給南牆外側
     
     for y in 0..total_height {
         for x in 0..width {
-            let world_x = (start_x + x as i32) as f32 * tile_size;
-            let world_y = (start_y + y as i32 - 1) as f32 * tile_size; // -1 調整南牆外側位置
+            let cell = IVec2::new(start_x + x as i32, start_y + y as i32 - 1); // -1 調整南牆外側位置
             
-            // 決定瓷磚類型和對應的圖片
-            let (tile_type, texture_handle) = if y == total_height - 1 {
+            // 決定瓷磚類型
+            let tile_type = if doors.contains(&cell) {
+                // 門取代牆壁
+                RoomTileType::DoorClosed
+            } else if y == 1 && doors.contains(&(cell - IVec2::Y)) {
+                // 南牆的門後方內側牆改為地板，讓門通往房間內部
+                RoomTileType::Floor
+            } else if y == total_height - 1 {
                 // 北牆（上方，面向玩家）
                 if x == 0 {
-                    (RoomTileType::WallNInnerCornerW, room_assets.wall_n_inner_corner_w.clone())
+                    RoomTileType::WallNInnerCornerW
                 } else if x == width - 1 {
-                    (RoomTileType::WallNInnerCornerE, room_assets.wall_n_inner_corner_e.clone())
+                    RoomTileType::WallNInnerCornerE
                 } else {
-                    (RoomTileType::WallNInnerMid, room_assets.wall_n_inner_mid.clone())
+                    RoomTileType::WallNInnerMid
                 }
             } else if y == 1 {
                 // 南牆內側
                 if x == 0 {
-                    (RoomTileType::WallSInnerCapL, room_assets.wall_s_inner_cap_l.clone())
+                    RoomTileType::WallSInnerCapL
                 } else if x == width - 1 {
-                    (RoomTileType::WallSInnerCapR, room_assets.wall_s_inner_cap_r.clone())
+                    RoomTileType::WallSInnerCapR
                 } else {
-                    (RoomTileType::WallSInnerMid, room_assets.wall_s_inner_mid.clone())
+                    RoomTileType::WallSInnerMid
                 }
             } else if y == 0 {
                 // 南牆外側
-                let door_x = width / 2; // 門在牆壁中間
                 if x == 0 {
-                    (RoomTileType::WallSOuterCapL, room_assets.wall_s_outer_cap_l.clone())
+                    RoomTileType::WallSOuterCapL
                 } else if x == width - 1 {
-                    (RoomTileType::WallSOuterCapR, room_assets.wall_s_outer_cap_r.clone())
-                } else if x == door_x && should_generate_door {
-                    // 只有在應該生成門時才在中間位置放置門
-                    (RoomTileType::DoorClosed, room_assets.door_closed.clone())
+                    RoomTileType::WallSOuterCapR
                 } else {
-                    (RoomTileType::WallSOuterMid, room_assets.wall_s_outer_mid.clone())
+                    RoomTileType::WallSOuterMid
                 }
             } else if x == 0 {
                 // 西側牆
-                (RoomTileType::WallWSide, room_assets.wall_w_side.clone())
+                RoomTileType::WallWSide
             } else if x == width - 1 {
                 // 東側牆
-                (RoomTileType::WallESide, room_assets.wall_e_side.clone())
+                RoomTileType::WallESide
             } else {
                 // 內部地板
-                (RoomTileType::Floor, room_assets.floor_indoor.clone())
+                RoomTileType::Floor
             };
             
-            // 生成瓷磚實體
-            let mut entity_commands = commands.spawn((
-                Sprite::from_image(texture_handle),
-                Transform::from_translation(Vec3::new(world_x, world_y, Z_LAYER_GRID + 0.1))
-                    .with_scale(Vec3::splat(PLAYER_SCALE)), // 使用與玩家相同的縮放
-                RoomTile { tile_type },
-            ));
-            
-            // 如果是門，添加Door組件
-            if matches!(tile_type, RoomTileType::DoorClosed | RoomTileType::DoorOpen) {
-                entity_commands.insert(Door { 
-                    is_open: tile_type == RoomTileType::DoorOpen 
-                });
-            }
+            grid.place(cell, tile_type);
         }
     }
 }
 
-// 走廊連接系統
-fn generate_corridors(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
-    compound_room: &CompoundRoom,
-) {
+// 走廊連接系統 - 回傳複合房間各矩形之間需要打通的格子
+fn generate_corridors(compound_room: &CompoundRoom) -> Vec<IVec2> {
     if compound_room.rectangles.len() < 2 {
-        return; // 不需要走廊
+        return Vec::new(); // 不需要走廊
     }
     
-    let room_assets = RoomAssets::load_all(asset_server);
-    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
-    
     // 為每對相鄰房間創建連接
     match compound_room.room_type {
-        CompoundRoomType::LShape => {
-            create_l_shape_corridor(commands, &room_assets, tile_size, &compound_room.rectangles);
-        },
-        CompoundRoomType::TShape => {
-            create_t_shape_corridor(commands, &room_assets, tile_size, &compound_room.rectangles);
-        },
-        CompoundRoomType::Plus => {
-            create_plus_shape_corridors(commands, &room_assets, tile_size, &compound_room.rectangles);
-        },
-        _ => {}
+        CompoundRoomType::LShape => create_l_shape_corridor(&compound_room.rectangles),
+        CompoundRoomType::TShape => create_t_shape_corridor(&compound_room.rectangles),
+        CompoundRoomType::Plus => create_plus_shape_corridors(&compound_room.rectangles),
+        _ => Vec::new(),
     }
 }
 
 // L 形房間走廊
-fn create_l_shape_corridor(
-    commands: &mut Commands,
-    room_assets: &RoomAssets,
-    tile_size: f32,
-    rectangles: &[RoomRect],
-) {
+fn create_l_shape_corridor(rectangles: &[RoomRect]) -> Vec<IVec2> {
     if rectangles.len() != 2 {
-        return;
+        return Vec::new();
     }
     
     let main_rect = &rectangles[0];  // 主房間（垂直）
     let ext_rect = &rectangles[1];   // 擴展房間（水平）
     
     // 在兩個房間的連接處移除牆壁，創建開口
-    // 找到重疊區域的中心位置
     let corridor_x = main_rect.x + main_rect.width as i32 - 1; // 主房間右邊界
     let corridor_y = ext_rect.y + 1; // 擴展房間內部
     
-    // 移除牆壁，用地板取代（簡單版本：直接放地板）
-    let world_x = corridor_x as f32 * tile_size;
-    let world_y = corridor_y as f32 * tile_size;
-    
-    commands.spawn((
-        Sprite::from_image(room_assets.floor_indoor.clone()),
-        Transform::from_translation(Vec3::new(world_x, world_y, Z_LAYER_GRID + 0.2)) // 更高層級覆蓋牆壁
-            .with_scale(Vec3::splat(PLAYER_SCALE)),
-        RoomTile { tile_type: RoomTileType::Floor },
-    ));
+    vec![IVec2::new(corridor_x, corridor_y)]
 }
 
 // T 形房間走廊
-fn create_t_shape_corridor(
-    commands: &mut Commands,
-    room_assets: &RoomAssets,
-    tile_size: f32,
-    rectangles: &[RoomRect],
-) {
+fn create_t_shape_corridor(rectangles: &[RoomRect]) -> Vec<IVec2> {
     if rectangles.len() != 2 {
-        return;
+        return Vec::new();
     }
     
     let _top_rect = &rectangles[0];    // 上橫梁
@@ -235,44 +170,24 @@ fn create_t_shape_corridor(
     let corridor_x = stem_rect.x + stem_rect.width as i32 / 2; // 豎梁中心
     let corridor_y = stem_rect.y + stem_rect.height as i32 - 1; // 豎梁上部
     
-    let world_x = corridor_x as f32 * tile_size;
-    let world_y = corridor_y as f32 * tile_size;
-    
-    commands.spawn((
-        Sprite::from_image(room_assets.floor_indoor.clone()),
-        Transform::from_translation(Vec3::new(world_x, world_y, Z_LAYER_GRID + 0.2))
-            .with_scale(Vec3::splat(PLAYER_SCALE)),
-        RoomTile { tile_type: RoomTileType::Floor },
-    ));
+    vec![IVec2::new(corridor_x, corridor_y)]
 }
 
 // 十字形房間走廊
-fn create_plus_shape_corridors(
-    commands: &mut Commands,
-    room_assets: &RoomAssets,
-    tile_size: f32,
-    rectangles: &[RoomRect],
-) {
+fn create_plus_shape_corridors(rectangles: &[RoomRect]) -> Vec<IVec2> {
     if rectangles.len() != 5 {
-        return;
+        return Vec::new();
     }
     
     let center_rect = &rectangles[0]; // 中心區域
     
     // 為每個臂膀創建到中心的連接
-    for arm_rect in &rectangles[1..] {
-        let (corridor_x, corridor_y) = get_connection_point(center_rect, arm_rect);
-        
-        let world_x = corridor_x as f32 * tile_size;
-        let world_y = corridor_y as f32 * tile_size;
-        
-        commands.spawn((
-            Sprite::from_image(room_assets.floor_indoor.clone()),
-            Transform::from_translation(Vec3::new(world_x, world_y, Z_LAYER_GRID + 0.2))
-                .with_scale(Vec3::splat(PLAYER_SCALE)),
-            RoomTile { tile_type: RoomTileType::Floor },
-        ));
-    }
+    rectangles[1..].iter()
+        .map(|arm_rect| {
+            let (corridor_x, corridor_y) = get_connection_point(center_rect, arm_rect);
+            IVec2::new(corridor_x, corridor_y)
+        })
+        .collect()
 }
 
 // 獲取兩個房間之間的連接點
@@ -424,41 +339,141 @@ fn generate_plus_shape_room(rng: &mut impl Rng) -> CompoundRoom {
     }
 }
 
-// 複合房間生成函數 - 使用走廊連接方法
-fn spawn_compound_room(
+/// 把複合房間（含門與內部開口）放到瓷磚格上
+pub fn build_room_grid(grid: &mut TileGrid, compound_room: &CompoundRoom, doors: &[IVec2]) {
+    // 1. 為每個矩形生成完整房間，門只會出現在對應的牆上
+    for rect in &compound_room.rectangles {
+        generate_room_tiles(grid, rect.width, rect.height, rect.x, rect.y, doors);
+    }
+    
+    // 2. 打通矩形之間的牆壁
+    for cell in generate_corridors(compound_room) {
+        grid.place(cell, RoomTileType::Floor);
+    }
+}
+
+/// 依瓷磚格生成瓷磚實體，門會額外加上 Door 組件，有標記區域的格子加上 FloorRegion
+pub fn spawn_tile_grid(
     commands: &mut Commands,
-    asset_server: &AssetServer,
-    compound_room: CompoundRoom,
+    room_assets: &RoomAssets,
+    grid: &TileGrid,
+    regions: &HashMap<IVec2, FloorRegion>,
 ) {
-    // 1. 找到y座標最小的矩形（最下面的房間）
-    let bottommost_rect_index = compound_room.rectangles
-        .iter()
-        .enumerate()
-        .min_by_key(|(_, rect)| rect.y)
-        .map(|(index, _)| index)
-        .unwrap_or(0);
+    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;  // 使用房間瓷磚專用尺寸並考慮縮放
     
-    // 2. 為每個矩形生成完整房間，只在最下面的矩形生成門
-    for (index, rect) in compound_room.rectangles.iter().enumerate() {
-        let should_generate_door = index == bottommost_rect_index; // 只在最下面的矩形生成門
-        generate_room_tiles(
-            commands,
-            asset_server,
-            rect.width,
-            rect.height,
-            rect.x,
-            rect.y,
-            should_generate_door,
-        );
+    for (cell, tile_type) in &grid.tiles {
+        let mut entity_commands = commands.spawn((
+            Sprite::from_image(room_assets.image_for(*tile_type)),
+            Transform::from_translation((cell.as_vec2() * tile_size).extend(Z_LAYER_GRID + 0.1))
+                .with_scale(Vec3::splat(PLAYER_SCALE)), // 使用與玩家相同的縮放
+            RoomTile { tile_type: *tile_type },
+            StateScoped(InRun),
+        ));
+
+        if let Some(region) = regions.get(cell) {
+            entity_commands.insert(*region);
+        }
+        
+        // 如果是門，添加Door組件
+        if tile_type.is_door() {
+            let door = Door::new(*cell, *tile_type == RoomTileType::DoorOpen);
+            let interactable = Interactable {
+                radius: DOOR_INTERACTION_RADIUS,
+                prompt: door_prompt(&door).to_string(),
+                requires_facing: true,
+            };
+            entity_commands.insert((door, interactable));
+        }
     }
-    
-    // 3. 生成連接走廊
-    generate_corridors(commands, asset_server, &compound_room);
-    
-    // 創建複合房間實體
-    commands.spawn((
-        compound_room,
-        Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-        Visibility::Visible,
-    ));
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::{HashSet, VecDeque};
+
+    const SEEDS: u64 = 5000;
+
+    fn reachable_from(grid: &TileGrid, start: IVec2) -> HashSet<IVec2> {
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            for step in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                let next = cell + step;
+                if grid.is_walkable(next) && visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        visited
+    }
+
+    #[test]
+    fn every_template_floor_is_reachable() {
+        for seed in 0..SEEDS {
+            let room = generate_room_template(&mut StdRng::seed_from_u64(seed));
+            let mut grid = TileGrid::default();
+            build_room_grid(&mut grid, &room, &[]);
+
+            let (min, max) = room.rectangles[0].floor_cells();
+            let reachable = reachable_from(&grid, (min + max) / 2);
+            for (cell, tile_type) in &grid.tiles {
+                if *tile_type == RoomTileType::Floor {
+                    assert!(reachable.contains(cell), "種子 {seed} 的 {:?} 房間有無法到達的地板 {cell}", room.room_type);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn walls_never_cover_template_floor() {
+        for seed in 0..SEEDS {
+            let room = generate_room_template(&mut StdRng::seed_from_u64(seed));
+            let mut grid = TileGrid::default();
+            build_room_grid(&mut grid, &room, &[]);
+
+            for rect in &room.rectangles {
+                let (min, max) = rect.floor_cells();
+                for y in min.y..=max.y {
+                    for x in min.x..=max.x {
+                        let cell = IVec2::new(x, y);
+                        assert_eq!(grid.get(cell), Some(RoomTileType::Floor), "種子 {seed} 的地板 {cell} 被牆壁覆蓋");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn room_tiles_follow_wall_layout() {
+        let mut grid = TileGrid::default();
+        let door = IVec2::new(0, -1);
+        generate_room_tiles(&mut grid, 5, 5, -2, 0, &[door]);
+
+        // 5x5 房間加上南牆外側共 5x6 格
+        assert_eq!(grid.tiles.len(), 30);
+        assert_eq!(grid.get(IVec2::new(-2, 4)), Some(RoomTileType::WallNInnerCornerW));
+        assert_eq!(grid.get(IVec2::new(2, 4)), Some(RoomTileType::WallNInnerCornerE));
+        assert_eq!(grid.get(IVec2::new(-1, 0)), Some(RoomTileType::WallSInnerMid));
+        assert_eq!(grid.get(IVec2::new(-2, -1)), Some(RoomTileType::WallSOuterCapL));
+        assert_eq!(grid.get(IVec2::new(-2, 2)), Some(RoomTileType::WallWSide));
+        assert_eq!(grid.get(IVec2::new(2, 2)), Some(RoomTileType::WallESide));
+        assert_eq!(grid.get(IVec2::new(0, 2)), Some(RoomTileType::Floor));
+        // 南牆的門：門後方的內側牆改為地板
+        assert_eq!(grid.get(door), Some(RoomTileType::DoorClosed));
+        assert_eq!(grid.get(IVec2::new(0, 0)), Some(RoomTileType::Floor));
+    }
+
+    #[test]
+    fn plus_arms_connect_on_center_walls() {
+        let center = RoomRect { x: -3, y: -3, width: 7, height: 7 };
+        let arm = |x, y| RoomRect { x, y, width: 3, height: 3 };
+
+        assert_eq!(get_connection_point(&center, &arm(-1, 5)), (0, 3));
+        assert_eq!(get_connection_point(&center, &arm(-1, -7)), (0, -3));
+        assert_eq!(get_connection_point(&center, &arm(5, -1)), (3, 0));
+        assert_eq!(get_connection_point(&center, &arm(-7, -1)), (-3, 0));
+    }
+}